@@ -0,0 +1,489 @@
+use clap::ValueEnum;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, Array, ArrayOfTables, DocumentMut, Item, Table};
+
+use crate::dep5::{self, Paragraph, DEP5_FORMAT_URL};
+use crate::scan::FileScan;
+use crate::walk::display_relative_path;
+
+pub const REUSE_TOML_FILE_BASENAME: &str = "REUSE.toml";
+pub const DEP5_FILE_RELATIVE_PATH: &str = ".reuse/dep5";
+const REUSE_TOML_VERSION: i64 = 1;
+const SPDX_FILE_COPYRIGHT_TEXT_KEY: &str = "SPDX-FileCopyrightText";
+const SPDX_LICENSE_IDENTIFIER_KEY: &str = "SPDX-License-Identifier";
+
+/// How an annotation is combined with the information found in the file itself
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Precedence {
+    /// The information in the file wins, the annotation only fills in what is missing
+    #[default]
+    Closest,
+    /// The information in the file and in the annotation are combined
+    Aggregate,
+    /// The annotation wins, the information in the file is ignored
+    Override,
+}
+
+impl Precedence {
+    fn as_str(&self) -> &str {
+        match self {
+            Precedence::Closest => "closest",
+            Precedence::Aggregate => "aggregate",
+            Precedence::Override => "override",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<OneOrMany> for Vec<String> {
+    fn from(one_or_many: OneOrMany) -> Self {
+        match one_or_many {
+            OneOrMany::One(one) => vec![one],
+            OneOrMany::Many(many) => many,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ReuseToml {
+    version: i64,
+    #[serde(default)]
+    annotations: Vec<ReuseTomlAnnotation>,
+}
+
+#[derive(Deserialize)]
+struct ReuseTomlAnnotation {
+    path: OneOrMany,
+    #[serde(default)]
+    precedence: Precedence,
+    #[serde(rename = "SPDX-FileCopyrightText")]
+    copyright_notices: Option<OneOrMany>,
+    #[serde(rename = "SPDX-License-Identifier")]
+    license_identifiers: Option<OneOrMany>,
+}
+
+struct Annotation {
+    path_globs: GlobSet,
+    precedence: Precedence,
+    copyright_notices: Vec<String>,
+    license_identifiers: Vec<String>,
+}
+
+impl Annotation {
+    // In `REUSE.toml`, `*` doesn't match `/` while `**` does. In `.reuse/dep5`, `*` matches `/` too.
+    fn new(
+        path_patterns: &[String],
+        is_separator_literal: bool,
+        precedence: Precedence,
+        copyright_notices: Vec<String>,
+        license_identifiers: Vec<String>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut path_globs_builder = GlobSetBuilder::new();
+        for path_pattern in path_patterns {
+            let path_glob = GlobBuilder::new(path_pattern)
+                .literal_separator(is_separator_literal)
+                .build()?;
+            path_globs_builder.add(path_glob);
+        }
+        Ok(Annotation {
+            path_globs: path_globs_builder.build()?,
+            precedence,
+            copyright_notices,
+            license_identifiers,
+        })
+    }
+
+    fn apply(&self, file_items: &[String], annotation_items: &[String]) -> Vec<String> {
+        match self.precedence {
+            Precedence::Closest if !file_items.is_empty() => file_items.to_vec(),
+            Precedence::Closest | Precedence::Override => annotation_items.to_vec(),
+            Precedence::Aggregate => {
+                let mut items = file_items.to_vec();
+                for annotation_item in annotation_items {
+                    if !items.contains(annotation_item) {
+                        items.push(annotation_item.clone());
+                    }
+                }
+                items
+            }
+        }
+    }
+}
+
+// Annotations provide copyright and license information for files that can't carry a header,
+// such as binary assets or generated files.
+#[derive(Default)]
+pub struct Annotations {
+    annotations: Vec<Annotation>,
+}
+
+impl Annotations {
+    pub fn load(root: &Path) -> Result<Self, Box<dyn Error>> {
+        let reuse_toml_path = root.join(REUSE_TOML_FILE_BASENAME);
+        let dep5_path = root.join(DEP5_FILE_RELATIVE_PATH);
+        match (reuse_toml_path.is_file(), dep5_path.is_file()) {
+            (true, true) => Err(render_conflicting_annotation_files_error().into()),
+            (true, false) => Annotations::from_reuse_toml(&fs::read_to_string(reuse_toml_path)?),
+            (false, true) => Annotations::from_dep5(&fs::read_to_string(dep5_path)?),
+            (false, false) => Ok(Annotations::default()),
+        }
+    }
+
+    fn from_reuse_toml(content: &str) -> Result<Self, Box<dyn Error>> {
+        let reuse_toml: ReuseToml = toml::from_str(content)?;
+        if reuse_toml.version != REUSE_TOML_VERSION {
+            return Err(format!(
+                "unsupported {} version: {}",
+                REUSE_TOML_FILE_BASENAME, reuse_toml.version
+            )
+            .into());
+        }
+        let mut annotations = Vec::new();
+        for reuse_toml_annotation in reuse_toml.annotations {
+            annotations.push(Annotation::new(
+                &Vec::from(reuse_toml_annotation.path),
+                true,
+                reuse_toml_annotation.precedence,
+                reuse_toml_annotation
+                    .copyright_notices
+                    .map(Vec::from)
+                    .unwrap_or_default(),
+                reuse_toml_annotation
+                    .license_identifiers
+                    .map(Vec::from)
+                    .unwrap_or_default(),
+            )?);
+        }
+        Ok(Annotations { annotations })
+    }
+
+    fn from_dep5(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut annotations = Vec::new();
+        for paragraph in dep5::parse(content) {
+            let Some(files) = paragraph.field("Files") else {
+                continue;
+            };
+            let path_patterns: Vec<String> = files
+                .split_whitespace()
+                .map(|file| file.to_string())
+                .collect();
+            let copyright_notices = paragraph
+                .field("Copyright")
+                .map(|copyright| {
+                    copyright
+                        .lines()
+                        .map(|line| line.trim().to_string())
+                        .filter(|line| !line.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            // Only the first line holds the license expression, the rest is an optional license text.
+            let license_identifiers = paragraph
+                .field("License")
+                .and_then(|license| license.lines().next())
+                .map(|expression| vec![expression.trim().to_string()])
+                .unwrap_or_default();
+            annotations.push(Annotation::new(
+                &path_patterns,
+                false,
+                Precedence::Aggregate,
+                copyright_notices,
+                license_identifiers,
+            )?);
+        }
+        Ok(Annotations { annotations })
+    }
+
+    // When several annotations match the same file, the last one wins.
+    pub fn apply(&self, root: &Path, path: &Path, file_scan: &FileScan) -> FileScan {
+        let relative_path = display_relative_path(root, path).replace('\\', "/");
+        let annotation_option = self
+            .annotations
+            .iter()
+            .rev()
+            .find(|annotation| annotation.path_globs.is_match(&relative_path));
+        if let Some(annotation) = annotation_option {
+            FileScan::new(
+                annotation.apply(
+                    file_scan.license_identifiers(),
+                    &annotation.license_identifiers,
+                ),
+                annotation.apply(file_scan.copyright_notices(), &annotation.copyright_notices),
+            )
+        } else {
+            file_scan.clone()
+        }
+    }
+}
+
+fn render_conflicting_annotation_files_error() -> String {
+    format!(
+        "both {} and {} exist, only one of them may be used",
+        REUSE_TOML_FILE_BASENAME, DEP5_FILE_RELATIVE_PATH
+    )
+}
+
+fn to_toml_item(items: &[String]) -> Item {
+    if let [item] = items {
+        value(item.as_str())
+    } else {
+        value(items.iter().collect::<Array>())
+    }
+}
+
+// Existing content of `REUSE.toml`, including comments and formatting, is kept as is.
+pub fn append_reuse_toml_annotation(
+    root: &Path,
+    path_patterns: &[String],
+    precedence: Precedence,
+    copyright_notices: &[String],
+    license_expression: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if root.join(DEP5_FILE_RELATIVE_PATH).is_file() {
+        return Err(render_conflicting_annotation_files_error().into());
+    }
+    let reuse_toml_path = root.join(REUSE_TOML_FILE_BASENAME);
+    let mut reuse_toml = if reuse_toml_path.is_file() {
+        fs::read_to_string(&reuse_toml_path)?.parse::<DocumentMut>()?
+    } else {
+        let mut reuse_toml = DocumentMut::new();
+        reuse_toml["version"] = value(REUSE_TOML_VERSION);
+        reuse_toml
+    };
+    let mut annotation = Table::new();
+    annotation["path"] = to_toml_item(path_patterns);
+    annotation["precedence"] = value(precedence.as_str());
+    if !copyright_notices.is_empty() {
+        annotation[SPDX_FILE_COPYRIGHT_TEXT_KEY] = to_toml_item(copyright_notices);
+    }
+    annotation[SPDX_LICENSE_IDENTIFIER_KEY] = value(license_expression);
+    reuse_toml
+        .entry("annotations")
+        .or_insert(Item::ArrayOfTables(ArrayOfTables::new()))
+        .as_array_of_tables_mut()
+        .ok_or("`annotations` must be an array of tables")?
+        .push(annotation);
+    fs::write(&reuse_toml_path, reuse_toml.to_string())?;
+    Ok(reuse_toml_path)
+}
+
+// Unlike in `REUSE.toml`, the `Copyright` field is mandatory in a `Files` paragraph.
+pub fn append_dep5_paragraph(
+    root: &Path,
+    path_patterns: &[String],
+    copyright_notices: &[String],
+    license_expression: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if root.join(REUSE_TOML_FILE_BASENAME).is_file() {
+        return Err(render_conflicting_annotation_files_error().into());
+    }
+    if copyright_notices.is_empty() {
+        return Err("a copyright notice is required in `.reuse/dep5`".into());
+    }
+    let dep5_path = root.join(DEP5_FILE_RELATIVE_PATH);
+    // The paragraph is appended to the existing text, so that its comments are kept.
+    let mut dep5_content = if dep5_path.is_file() {
+        fs::read_to_string(&dep5_path)?
+    } else {
+        let mut header_paragraph = Paragraph::new();
+        header_paragraph.push_field("Format", DEP5_FORMAT_URL);
+        dep5::render(&[header_paragraph])
+    };
+    let mut files_paragraph = Paragraph::new();
+    files_paragraph.push_field("Files", &path_patterns.join(" "));
+    files_paragraph.push_field("Copyright", &copyright_notices.join("\n"));
+    files_paragraph.push_field("License", license_expression);
+    dep5_content.truncate(dep5_content.trim_end().len());
+    if !dep5_content.is_empty() {
+        dep5_content.push_str("\n\n");
+    }
+    dep5_content.push_str(&dep5::render(&[files_paragraph]));
+    fs::create_dir_all(dep5_path.parent().unwrap())?;
+    fs::write(&dep5_path, dep5_content)?;
+    Ok(dep5_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_to_file(
+        annotations: &Annotations,
+        relative_path: &str,
+        license_identifiers: &[&str],
+    ) -> FileScan {
+        let root = Path::new("/project");
+        let file_scan = FileScan::new(
+            license_identifiers
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            Vec::new(),
+        );
+        annotations.apply(root, &root.join(relative_path), &file_scan)
+    }
+
+    #[test]
+    fn apply_reuse_toml_annotations() {
+        let annotations = Annotations::from_reuse_toml(
+            r#"
+version = 1
+
+[[annotations]]
+path = ["assets/*.png", "docs/**"]
+SPDX-FileCopyrightText = "2024 Jane Doe"
+SPDX-License-Identifier = "CC0-1.0"
+
+[[annotations]]
+path = "docs/generated/**"
+precedence = "override"
+SPDX-License-Identifier = "MIT"
+"#,
+        )
+        .unwrap();
+        let logo_scan = apply_to_file(&annotations, "assets/logo.png", &[]);
+        assert_eq!(
+            logo_scan.license_identifiers(),
+            &vec!["CC0-1.0".to_string()]
+        );
+        assert_eq!(
+            logo_scan.copyright_notices(),
+            &vec!["2024 Jane Doe".to_string()]
+        );
+        // `*` doesn't match `/` in `REUSE.toml`.
+        let nested_logo_scan = apply_to_file(&annotations, "assets/icons/logo.png", &[]);
+        assert!(nested_logo_scan.license_identifiers().is_empty());
+        // With the default `closest` precedence the header of the file wins.
+        let guide_scan = apply_to_file(&annotations, "docs/guide.md", &["CC-BY-4.0"]);
+        assert_eq!(
+            guide_scan.license_identifiers(),
+            &vec!["CC-BY-4.0".to_string()]
+        );
+        let api_scan = apply_to_file(&annotations, "docs/generated/api.md", &["CC-BY-4.0"]);
+        assert_eq!(api_scan.license_identifiers(), &vec!["MIT".to_string()]);
+    }
+
+    #[test]
+    fn apply_dep5_annotations() {
+        let annotations = Annotations::from_dep5(
+            "\
+Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+
+Files: assets/*
+Copyright: 2024 Jane Doe
+License: CC0-1.0
+",
+        )
+        .unwrap();
+        // `*` matches `/` in `.reuse/dep5`, and the information is aggregated.
+        let logo_scan = apply_to_file(&annotations, "assets/icons/logo.png", &["MIT"]);
+        assert_eq!(
+            logo_scan.license_identifiers(),
+            &vec!["MIT".to_string(), "CC0-1.0".to_string()]
+        );
+    }
+
+    #[test]
+    fn append_reuse_toml_annotation_keeps_existing_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let reuse_toml_path = temp_dir.path().join(REUSE_TOML_FILE_BASENAME);
+        fs::write(&reuse_toml_path, "# Assets\nversion = 1\n").unwrap();
+        append_reuse_toml_annotation(
+            temp_dir.path(),
+            &["assets/**".to_string()],
+            Precedence::Aggregate,
+            &["2024 Jane Doe".to_string()],
+            "CC0-1.0",
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(reuse_toml_path).unwrap(),
+            "\
+# Assets
+version = 1
+
+[[annotations]]
+path = \"assets/**\"
+precedence = \"aggregate\"
+SPDX-FileCopyrightText = \"2024 Jane Doe\"
+SPDX-License-Identifier = \"CC0-1.0\"
+"
+        );
+    }
+
+    #[test]
+    fn append_annotation_with_conflicting_annotation_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        append_dep5_paragraph(
+            temp_dir.path(),
+            &["assets/*".to_string()],
+            &["2024 Jane Doe".to_string()],
+            "CC0-1.0",
+        )
+        .unwrap();
+        assert!(append_reuse_toml_annotation(
+            temp_dir.path(),
+            &["assets/*".to_string()],
+            Precedence::Closest,
+            &[],
+            "CC0-1.0"
+        )
+        .is_err());
+        assert!(Annotations::load(temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn append_dep5_paragraph_keeps_comments() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dep5_path = temp_dir.path().join(DEP5_FILE_RELATIVE_PATH);
+        fs::create_dir_all(dep5_path.parent().unwrap()).unwrap();
+        fs::write(
+            &dep5_path,
+            "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n\n# Generated by the build\nFiles: gen/*\nCopyright: 2023 Jane Doe\nLicense: MIT\n\n",
+        )
+        .unwrap();
+        append_dep5_paragraph(
+            temp_dir.path(),
+            &["assets/*".to_string()],
+            &["2024 Jane Doe".to_string()],
+            "CC0-1.0",
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&dep5_path).unwrap(),
+            "\
+Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+
+# Generated by the build
+Files: gen/*
+Copyright: 2023 Jane Doe
+License: MIT
+
+Files: assets/*
+Copyright: 2024 Jane Doe
+License: CC0-1.0
+"
+        );
+    }
+
+    #[test]
+    fn append_dep5_paragraph_without_copyright() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(
+            append_dep5_paragraph(temp_dir.path(), &["assets/*".to_string()], &[], "CC0-1.0")
+                .is_err()
+        );
+        assert!(!temp_dir.path().join(DEP5_FILE_RELATIVE_PATH).exists());
+    }
+}
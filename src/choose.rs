@@ -0,0 +1,243 @@
+use std::error::Error;
+use std::io::{BufRead, Write};
+
+use crate::core::{License, Licenses};
+use crate::metadata::{Condition, Copyleft, Permission};
+
+struct Choice {
+    key: &'static str,
+    label: &'static str,
+    matches: fn(&License) -> bool,
+}
+
+struct Question {
+    text: &'static str,
+    choices: Vec<Choice>,
+}
+
+fn create_questions() -> Vec<Question> {
+    vec![
+        Question {
+            text: "Under which license can modified versions be distributed?",
+            choices: vec![
+                Choice {
+                    key: "p",
+                    label: "Any license, even proprietary (permissive)",
+                    matches: |license| license.copyleft() == Copyleft::None,
+                },
+                Choice {
+                    key: "w",
+                    label: "The same license for the modified files or library only (weak copyleft)",
+                    matches: |license| license.copyleft() == Copyleft::Weak,
+                },
+                Choice {
+                    key: "s",
+                    label: "The same license for the whole work (strong copyleft)",
+                    matches: |license| license.copyleft() == Copyleft::Strong,
+                },
+            ],
+        },
+        Question {
+            text: "Should contributors explicitly grant their patent rights to users?",
+            choices: vec![
+                Choice {
+                    key: "y",
+                    label: "Yes",
+                    matches: |license| license.permissions().contains(&Permission::PatentUse),
+                },
+                Choice {
+                    key: "n",
+                    label: "No",
+                    matches: |license| !license.permissions().contains(&Permission::PatentUse),
+                },
+            ],
+        },
+        Question {
+            text: "Should users interacting with the software over a network be able to get its source?",
+            choices: vec![
+                Choice {
+                    key: "y",
+                    label: "Yes",
+                    matches: |license| {
+                        license
+                            .conditions()
+                            .contains(&Condition::NetworkUseDisclose)
+                    },
+                },
+                Choice {
+                    key: "n",
+                    label: "No",
+                    matches: |license| {
+                        !license
+                            .conditions()
+                            .contains(&Condition::NetworkUseDisclose)
+                    },
+                },
+            ],
+        },
+        Question {
+            text: "Should software under other licenses be able to link to your code as a library?",
+            choices: vec![
+                Choice {
+                    key: "y",
+                    label: "Yes",
+                    matches: |license| license.copyleft() != Copyleft::Strong,
+                },
+                Choice {
+                    key: "n",
+                    label: "No",
+                    matches: |license| license.copyleft() == Copyleft::Strong,
+                },
+            ],
+        },
+    ]
+}
+
+// Returns the trimmed line, `None` at the end of the input.
+fn read_answer<R: BufRead>(input: &mut R) -> Result<Option<String>, Box<dyn Error>> {
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(answer.trim().to_lowercase()))
+}
+
+// Asks the question with the choices that keep at least one candidate, and returns the candidates
+// matching the answer, all of them if there is no preference. The question is skipped if it can't
+// narrow the candidates, i.e. if one of the choices matches all of them.
+fn ask_question<'a, R: BufRead, W: Write>(
+    question: &Question,
+    candidates: Vec<&'a License>,
+    input: &mut R,
+    output: &mut W,
+) -> Result<Vec<&'a License>, Box<dyn Error>> {
+    let choices: Vec<(&Choice, usize)> = question
+        .choices
+        .iter()
+        .map(|choice| {
+            let matches_count = candidates
+                .iter()
+                .filter(|license| (choice.matches)(license))
+                .count();
+            (choice, matches_count)
+        })
+        .filter(|(_, matches_count)| *matches_count > 0)
+        .collect();
+    if choices
+        .iter()
+        .any(|(_, matches_count)| *matches_count == candidates.len())
+    {
+        return Ok(candidates);
+    }
+    writeln!(output, "{}", question.text)?;
+    for (choice, matches_count) in &choices {
+        writeln!(
+            output,
+            "  {}) {} [{}]",
+            choice.key, choice.label, matches_count
+        )?;
+    }
+    loop {
+        write!(output, "Answer (Enter for no preference): ")?;
+        output.flush()?;
+        let answer = match read_answer(input)? {
+            Some(answer) if !answer.is_empty() => answer,
+            _ => {
+                writeln!(output)?;
+                return Ok(candidates);
+            }
+        };
+        if let Some((choice, _)) = choices.iter().find(|(choice, _)| choice.key == answer) {
+            writeln!(output)?;
+            return Ok(candidates
+                .into_iter()
+                .filter(|license| (choice.matches)(license))
+                .collect());
+        }
+        let keys: Vec<&str> = choices.iter().map(|(choice, _)| choice.key).collect();
+        writeln!(output, "Please answer with one of: {}", keys.join(", "))?;
+    }
+}
+
+// Narrows the licenses of the catalog with the answers, then asks which one to
+// add, returning `None` if the user doesn't pick any.
+pub fn choose_license<'a, R: BufRead, W: Write>(
+    licenses: &'a Licenses,
+    input: &mut R,
+    output: &mut W,
+) -> Result<Option<&'a License>, Box<dyn Error>> {
+    let mut candidates: Vec<&License> = licenses.licenses().iter().collect();
+    for question in create_questions() {
+        candidates = ask_question(&question, candidates, input, output)?;
+    }
+    writeln!(output, "Matching licenses:")?;
+    for license in &candidates {
+        writeln!(output, "  {: <16}{}", license.name(), license.full_name())?;
+    }
+    loop {
+        write!(
+            output,
+            "License to add to the current directory (Enter to quit): "
+        )?;
+        output.flush()?;
+        let answer = match read_answer(input)? {
+            Some(answer) if !answer.is_empty() => answer,
+            _ => return Ok(None),
+        };
+        if let Some(license) = candidates.iter().find(|license| license.name() == answer) {
+            return Ok(Some(license));
+        }
+        writeln!(output, "Please enter one of the matching licenses")?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn choose_license_name(answers: &str) -> Option<String> {
+        let licenses = Licenses::from_description_file();
+        let mut output = Vec::new();
+        choose_license(&licenses, &mut Cursor::new(answers), &mut output)
+            .unwrap()
+            .map(|license| license.name().to_string())
+    }
+
+    #[test]
+    fn choose_license_with_answers() {
+        assert_eq!(
+            choose_license_name("s\n\ny\nagpl-3.0\n"),
+            Some("agpl-3.0".to_string())
+        );
+        assert_eq!(
+            choose_license_name("p\ny\napache-2.0\n"),
+            Some("apache-2.0".to_string())
+        );
+        assert_eq!(
+            choose_license_name("x\np\ny\nmit\napache-2.0\n"),
+            Some("apache-2.0".to_string())
+        );
+        assert_eq!(choose_license_name(""), None);
+    }
+
+    #[test]
+    fn ask_question_skips_question_that_does_not_narrow_candidates() {
+        let licenses = Licenses::from_description_file();
+        let candidates = vec![
+            licenses.find_license("mit").unwrap(),
+            licenses.find_license("bsd-2-clause").unwrap(),
+        ];
+        let mut output = Vec::new();
+        let questions = create_questions();
+        let remaining_candidates = ask_question(
+            &questions[2],
+            candidates,
+            &mut Cursor::new("y\n"),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(remaining_candidates.len(), 2);
+        assert!(output.is_empty());
+    }
+}
@@ -1,7 +1,16 @@
 use chrono::Datelike;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use configparser::ini::Ini;
 use std::env;
+use std::path::PathBuf;
+
+use crate::annotations::Precedence;
+use crate::headers::HeaderStyle;
+use crate::metadata::Copyleft;
+use crate::notices::NoticesFormat;
+use crate::output::OutputFormat;
+use crate::sbom::SbomFormat;
+use crate::table::{ListColumn, ListSort};
 
 pub const LICENSE_ARG: &str = "LICENSE";
 pub const LICENSE_AUTHOR_ENV_VARIABLE_NAME: &str = "LICENSE_AUTHOR";
@@ -10,6 +19,9 @@ pub const LICENSE_AUTHOR_ENV_VARIABLE_NAME: &str = "LICENSE_AUTHOR";
 #[derive(Parser)]
 #[command(version)]
 pub struct Cli {
+    /// Format of the output of `list`, `show` and `info`
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -17,7 +29,12 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Print a list of all available licenses
-    List,
+    List {
+        #[command(flatten)]
+        filters: ListFilters,
+        #[command(flatten)]
+        layout: ListLayout,
+    },
     /// Print the content of the selected license
     Show {
         /// Selected license
@@ -32,22 +49,292 @@ pub enum Commands {
         /// License template only, no fillers for user or organization and year
         #[arg(short = 't', long = "template")]
         is_template: bool,
+        /// Identifiers of the license in package ecosystems instead of its text
+        #[arg(short = 'i', long = "ids", conflicts_with_all = ["author", "year", "is_template"])]
+        is_ids: bool,
+        /// Short plain-language summary of the license instead of its text
+        #[arg(short = 's', long = "summary", conflicts_with_all = ["author", "year", "is_template", "is_ids"])]
+        is_summary: bool,
+    },
+    /// Print the permissions, conditions and limitations of the selected license
+    Info {
+        /// Selected license
+        #[arg(value_name = LICENSE_ARG)]
+        name: String,
+    },
+    /// Print the differences between the properties, permissions, conditions and limitations of licenses
+    Compare {
+        /// Selected licenses
+        #[arg(value_name = LICENSE_ARG, num_args = 2.., required = true)]
+        names: Vec<String>,
+        /// Also print the rows whose values are the same for all licenses
+        #[arg(short, long = "all")]
+        is_all: bool,
+    },
+    /// Print a word-level diff between the templates of two licenses
+    Diff {
+        /// License to compare from
+        #[arg(value_name = "OLD_LICENSE")]
+        old_name: String,
+        /// License to compare to
+        #[arg(value_name = "NEW_LICENSE")]
+        new_name: String,
+    },
+    /// Print the licenses whose template matches a regular expression, with the matching lines
+    Search {
+        /// Regular expression, which can match across lines, e.g. `patent\s+rights`
+        #[arg(value_name = "PATTERN")]
+        pattern: String,
+        /// Selected licenses, all of them by default
+        #[arg(value_name = LICENSE_ARG)]
+        names: Vec<String>,
+        /// Match regardless of case
+        #[arg(short = 'i', long = "ignore-case")]
+        is_case_insensitive: bool,
+        /// Number of lines to print around each matching line
+        #[arg(short = 'C', long = "context", value_name = "NUM", default_value_t = 1)]
+        context_lines_count: usize,
+        /// Only print the matching licenses and their number of matches
+        #[arg(short = 'l', long = "list")]
+        is_list: bool,
     },
     /// Add the selected license to the current directory
     Add {
+        /// Selected license [default: `license` of `.licensit.toml`]
+        #[arg(value_name = LICENSE_ARG)]
+        name: Option<String>,
+        /// The user or organization who holds the license [default: `holders` of `.licensit.toml`, `LICENSE_AUTHOR`, Git user name or current user]
+        #[arg(short = 'u', long = "user", value_name = "USER")]
+        author: Option<String>,
+        /// The year the license is in effect [default: `year` of `.licensit.toml` or current year]
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Also set the license in the package manifests of the current directory
+        #[arg(short, long = "sync-manifest")]
+        is_manifest_synced: bool,
+    },
+    /// Answer a few questions to pick a license, then add it to the current directory
+    Choose {
+        /// The user or organization who holds the license [default: `holders` of `.licensit.toml`, `LICENSE_AUTHOR`, Git user name or current user]
+        #[arg(short = 'u', long = "user", value_name = "USER")]
+        author: Option<String>,
+        /// The year the license is in effect [default: `year` of `.licensit.toml` or current year]
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Also set the license in the package manifests of the current directory
+        #[arg(short, long = "sync-manifest")]
+        is_manifest_synced: bool,
+    },
+    /// Set the license in the package manifests of the current directory
+    SyncManifest {
         /// Selected license
+        #[arg(
+            value_name = LICENSE_ARG,
+            required_unless_present_any = ["license_file", "expression"]
+        )]
+        name: Option<String>,
+        /// Reference a custom license file instead of a license from the list
+        #[arg(
+            short,
+            long = "license-file",
+            value_name = "PATH",
+            conflicts_with_all = ["name", "expression"]
+        )]
+        license_file: Option<String>,
+        /// Set an SPDX license expression, such as `MIT OR Apache-2.0`, instead of a license from
+        /// the list
+        #[arg(short, long, value_name = "EXPRESSION", conflicts_with = "name")]
+        expression: Option<String>,
+    },
+    /// Check that the license files and the package manifests declare the same license
+    Consistency {
+        /// Directory of the project
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+    },
+    /// Generate a machine-readable `debian/copyright` file in the current directory
+    DebianCopyright {
+        /// Selected license, by default the one detected in the license files
         #[arg(value_name = LICENSE_ARG)]
-        name: String,
-        /// The user or organization who holds the license
+        name: Option<String>,
+        /// The user or organization who holds the copyright of files without a copyright notice
         #[arg(short = 'u', long = "user", value_name = "USER", default_value_t = determine_license_author())]
         author: String,
-        /// The year the license is in effect
+        /// The year of the copyright of files without a copyright notice
         #[arg(short, long, default_value_t = chrono::Utc::now().year() as u32)]
         year: u32,
+        /// Skip files matching the glob, in addition to `exclude` of `.licensit.toml`, `.gitignore` and `.licensitignore`
+        #[arg(short, long = "exclude", value_name = "GLOB")]
+        excludes: Vec<String>,
+        /// Read every file again instead of reusing the results of the previous run
+        #[arg(long = "no-cache", action = clap::ArgAction::SetFalse)]
+        use_cache: bool,
+    },
+    /// Print the licenses of the Cargo, npm, Python and Go dependencies, read from local sources only
+    Deps {
+        /// Directory of the project
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+    },
+    /// Write the licenses of the dependencies to a notices file for distribution
+    Notices {
+        /// Directory of the project
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+        /// Format of the notices file
+        #[arg(short = 'f', long, value_enum, value_name = "FORMAT", default_value_t = NoticesFormat::Text)]
+        notices_format: NoticesFormat,
+        /// Path of the notices file, by default `THIRD-PARTY-NOTICES` with the extension of the format in the directory
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Print a software bill of materials describing the project and the licenses of its dependencies
+    Sbom {
+        /// Directory of the project
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+        /// Format of the SBOM document
+        #[arg(short = 'f', long, value_enum, value_name = "FORMAT", default_value_t = SbomFormat::SpdxJson)]
+        sbom_format: SbomFormat,
+        /// Write the document to a file instead of the standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Embed the texts of the available licenses in the CycloneDX formats
+        #[arg(long = "license-text")]
+        embeds_license_texts: bool,
+    },
+    /// Check the licenses of the dependencies against the policy of `.licensit.toml`
+    Policy {
+        #[command(subcommand)]
+        command: PolicyCommands,
+    },
+    /// Report the dependencies whose licenses are known to be incompatible with the project license
+    Compatibility {
+        /// Directory of the project
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+        /// License of the project, by default the one detected in the license files
+        #[arg(short, long = "license", value_name = LICENSE_ARG)]
+        name: Option<String>,
+    },
+    /// Print files that lack an SPDX license header
+    Headers {
+        /// Directory to scan
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+        /// Skip files matching the glob, in addition to `exclude` of `.licensit.toml`, `.gitignore` and `.licensitignore`
+        #[arg(short, long = "exclude", value_name = "GLOB")]
+        excludes: Vec<String>,
+        /// Information a header must carry [default: `header-style` of `.licensit.toml` or `license`]
+        #[arg(short, long, value_enum)]
+        style: Option<HeaderStyle>,
+        /// Exit with a non-zero status if any file lacks a header
+        #[arg(short, long)]
+        check: bool,
+        /// Read every file again instead of reusing the results of the previous run
+        #[arg(long = "no-cache", action = clap::ArgAction::SetFalse)]
+        use_cache: bool,
+    },
+    /// Manage the compliance of the current project with the REUSE specification
+    Reuse {
+        #[command(subcommand)]
+        command: ReuseCommands,
+    },
+}
+
+// Filters of `list`, combined with each other.
+#[derive(Args)]
+pub struct ListFilters {
+    /// Only licenses approved by the Open Source Initiative
+    #[arg(long = "osi")]
+    pub is_osi_approved: bool,
+    /// Only licenses that are free according to the Free Software Foundation
+    #[arg(long = "fsf-libre")]
+    pub is_fsf_libre: bool,
+    /// Only licenses with this scope of copyleft
+    #[arg(long, value_enum, value_name = "SCOPE")]
+    pub copyleft: Option<Copyleft>,
+    /// Only permissive licenses, the same as `--copyleft none`
+    #[arg(long = "permissive", conflicts_with = "copyleft")]
+    pub is_permissive: bool,
+    /// Only deprecated licenses
+    #[arg(long = "deprecated")]
+    pub is_deprecated: bool,
+    /// Only licenses whose ID, SPDX identifier, full name or aliases contain the text, ignoring case
+    #[arg(short, long, value_name = "TEXT")]
+    pub search: Option<String>,
+}
+
+// Columns and order of the table printed by `list`.
+#[derive(Args)]
+pub struct ListLayout {
+    /// Columns to print, separated by commas, with a header line [default: id,name]
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Option<Vec<ListColumn>>,
+    /// Order of the licenses
+    #[arg(long, value_enum, default_value_t = ListSort::Id)]
+    pub sort: ListSort,
+    /// Reverse the order of the licenses
+    #[arg(short = 'r', long = "reverse")]
+    pub is_reverse: bool,
+}
+
+#[derive(Subcommand)]
+pub enum PolicyCommands {
+    /// Report the dependencies whose licenses are denied, not covered or need review
+    Check {
+        /// Directory of the project, in or below the one containing `.licensit.toml`
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReuseCommands {
+    /// Add license texts to the `LICENSES` directory of the current directory
+    Init {
+        /// Selected licenses, by default the ones referenced by the files of the current directory
+        #[arg(value_name = LICENSE_ARG)]
+        names: Vec<String>,
+    },
+    /// Annotate files that can't carry a header in `REUSE.toml` of the current directory
+    Annotate {
+        /// Files the annotation applies to, as globs relative to the current directory
+        #[arg(value_name = "GLOB", required = true)]
+        paths: Vec<String>,
+        /// SPDX license expression of the files
+        #[arg(short, long, value_name = "EXPRESSION")]
+        license: String,
+        /// Copyright notice of the files, required with `--dep5`
+        #[arg(
+            short,
+            long = "copyright",
+            value_name = "TEXT",
+            required_if_eq("is_dep5", "true")
+        )]
+        copyrights: Vec<String>,
+        /// How the annotation is combined with the information found in the files themselves
+        #[arg(short, long, value_enum, default_value_t = Precedence::Closest, conflicts_with = "is_dep5")]
+        precedence: Precedence,
+        /// Write to the legacy `.reuse/dep5` file instead of `REUSE.toml`
+        #[arg(long = "dep5")]
+        is_dep5: bool,
+    },
+    /// Report missing and unused license texts and files lacking copyright or license information
+    Lint {
+        /// Directory to check
+        #[arg(value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+        /// Skip files matching the glob, in addition to `exclude` of `.licensit.toml`, `.gitignore` and `.licensitignore`
+        #[arg(short, long = "exclude", value_name = "GLOB")]
+        excludes: Vec<String>,
+        /// Read every file again instead of reusing the results of the previous run
+        #[arg(long = "no-cache", action = clap::ArgAction::SetFalse)]
+        use_cache: bool,
     },
 }
 
-fn determine_license_author() -> String {
+pub fn determine_license_author() -> String {
     let license_author_env_variable_result = env::var(LICENSE_AUTHOR_ENV_VARIABLE_NAME);
     if let Ok(license_author_env_variable) = license_author_env_variable_result {
         license_author_env_variable
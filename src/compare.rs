@@ -0,0 +1,180 @@
+use colored::Colorize;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeSet;
+
+use crate::core::License;
+use crate::metadata::{Condition, Limitation, Permission};
+use crate::table::{render_bool, render_table, ListColumn};
+
+// Appends a heading row followed by a row per rule held by any of the licenses, if any.
+fn push_rule_rows<T: Copy + Ord>(
+    rows: &mut Vec<Vec<String>>,
+    heading: &str,
+    licenses: &[&License],
+    fetch_rules: fn(&License) -> &Vec<T>,
+    label: fn(&T) -> &str,
+) {
+    let rules: BTreeSet<T> = licenses
+        .iter()
+        .flat_map(|license| fetch_rules(license).iter().copied())
+        .collect();
+    if rules.is_empty() {
+        return;
+    }
+    rows.push(vec![heading.to_string()]);
+    for rule in rules {
+        let mut row = vec![format!("    {}", label(&rule))];
+        row.extend(
+            licenses
+                .iter()
+                .map(|license| render_bool(fetch_rules(license).contains(&rule))),
+        );
+        rows.push(row);
+    }
+}
+
+// A row per property of the licenses, and under a heading per kind of rule, a row per rule held
+// by any of them. Unless `is_all`, only the rows whose values differ are kept.
+pub fn render_comparison(licenses: &[&License], is_all: bool) -> String {
+    let mut header_row = vec![String::new()];
+    header_row.extend(licenses.iter().map(|license| license.name().to_string()));
+    let mut rows = vec![header_row];
+    for (label, column) in [
+        ("SPDX", ListColumn::Spdx),
+        ("Copyleft", ListColumn::Copyleft),
+        ("OSI approved", ListColumn::Osi),
+        ("FSF libre", ListColumn::FsfLibre),
+        ("Deprecated", ListColumn::Deprecated),
+    ] {
+        let mut row = vec![label.to_string()];
+        row.extend(licenses.iter().map(|license| column.render(license)));
+        rows.push(row);
+    }
+    push_rule_rows(
+        &mut rows,
+        "Permissions",
+        licenses,
+        License::permissions,
+        Permission::label,
+    );
+    push_rule_rows(
+        &mut rows,
+        "Conditions",
+        licenses,
+        License::conditions,
+        Condition::label,
+    );
+    push_rule_rows(
+        &mut rows,
+        "Limitations",
+        licenses,
+        License::limitations,
+        Limitation::label,
+    );
+    if !is_all {
+        let is_different = |row: &Vec<String>| row[1..].iter().any(|value| *value != row[1]);
+        // The SPDX identifiers are kept, as they always differ. Headings are kept if any of their
+        // rules is.
+        let mut kept_rows: Vec<Vec<String>> = vec![rows[0].clone()];
+        let mut pending_heading_option = None;
+        for row in rows.into_iter().skip(1) {
+            if row.len() == 1 {
+                pending_heading_option = Some(row);
+            } else if is_different(&row) {
+                if let Some(pending_heading) = pending_heading_option.take() {
+                    kept_rows.push(pending_heading);
+                }
+                kept_rows.push(row);
+            }
+        }
+        rows = kept_rows;
+    }
+    render_table(&rows, None)
+}
+
+// Word-level diff of the texts, in the format of `git diff --word-diff=plain`: removed words are
+// enclosed in `[-` and `-]`, added words in `{+` and `+}`, and colored if the output is a terminal.
+pub fn render_word_diff(old_text: &str, new_text: &str) -> String {
+    // The whitespace around the words of a change is kept out of the markers, and changes of
+    // whitespace only are printed as in the new text.
+    let render_change = |tag: ChangeTag, text: &str| {
+        let words = text.trim();
+        let (leading_whitespace, trailing_whitespace) =
+            text.split_at(text.len() - text.trim_start().len());
+        let trailing_whitespace = &trailing_whitespace[words.len()..];
+        match tag {
+            ChangeTag::Delete if words.is_empty() => String::new(),
+            ChangeTag::Delete => format!(
+                "{}{}{}",
+                leading_whitespace,
+                format!("[-{}-]", words).red(),
+                trailing_whitespace
+            ),
+            ChangeTag::Insert if !words.is_empty() => format!(
+                "{}{}{}",
+                leading_whitespace,
+                format!("{{+{}+}}", words).green(),
+                trailing_whitespace
+            ),
+            _ => text.to_string(),
+        }
+    };
+    let text_diff = TextDiff::from_words(old_text, new_text);
+    let mut rendered_diff = String::new();
+    let mut pending_tag = ChangeTag::Equal;
+    let mut pending_text = String::new();
+    // Whitespace following a change, merged into the next change if it is of the same kind.
+    let mut pending_whitespace = String::new();
+    for change in text_diff.iter_all_changes() {
+        let (tag, value) = (change.tag(), change.value());
+        if tag == ChangeTag::Equal && pending_tag != ChangeTag::Equal && value.trim().is_empty() {
+            pending_whitespace.push_str(value);
+            continue;
+        }
+        if tag == pending_tag {
+            pending_text.push_str(&pending_whitespace);
+        } else {
+            rendered_diff.push_str(&render_change(pending_tag, &pending_text));
+            rendered_diff.push_str(&pending_whitespace);
+            pending_text.clear();
+            pending_tag = tag;
+        }
+        pending_whitespace.clear();
+        pending_text.push_str(value);
+    }
+    rendered_diff.push_str(&render_change(pending_tag, &pending_text));
+    rendered_diff.push_str(&pending_whitespace);
+    rendered_diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Licenses;
+
+    #[test]
+    fn render_comparison_with_differences_only() {
+        let licenses = Licenses::from_description_file();
+        let compared_licenses = vec![
+            licenses.find_license("mit").unwrap(),
+            licenses.find_license("bsd-2-clause").unwrap(),
+        ];
+        assert_eq!(
+            render_comparison(&compared_licenses, false),
+            "        mit    bsd-2-clause\nSPDX    MIT    BSD-2-Clause\n"
+        );
+        assert!(render_comparison(&compared_licenses, true).contains("\nPermissions\n"));
+    }
+
+    #[test]
+    fn render_word_diff_with_changes() {
+        colored::control::set_override(false);
+        assert_eq!(
+            render_word_diff(
+                "The above copyright notice shall be included.\n",
+                "The copyright notice shall be included in all copies.\n"
+            ),
+            "The [-above-] copyright notice shall be [-included.-]{+included in all copies.+}\n"
+        );
+    }
+}
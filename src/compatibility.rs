@@ -0,0 +1,188 @@
+use crate::core::License;
+use crate::deps::Dependency;
+use crate::manifests::DeclaredLicense;
+use crate::spdx::{parse_expression, Expression};
+
+// Returns the licenses of the expression that are known to be incompatible with the project
+// license, with the explanation. The expression is compatible if this is empty: a single
+// compatible alternative of `OR` is enough, while every operand of `AND` must be compatible.
+fn find_incompatibilities<'a>(
+    project_license: &'a License,
+    expression: &Expression,
+) -> Vec<(String, &'a str)> {
+    match expression {
+        Expression::License(identifier) => project_license
+            .find_incompatibility(identifier)
+            .map(|incompatibility| vec![(identifier.to_string(), incompatibility.reason())])
+            .unwrap_or_default(),
+        Expression::With(license, _) => find_incompatibilities(project_license, license),
+        Expression::Or(operands) => {
+            let mut incompatibilities = Vec::new();
+            for operand in operands {
+                let operand_incompatibilities = find_incompatibilities(project_license, operand);
+                if operand_incompatibilities.is_empty() {
+                    return Vec::new();
+                }
+                incompatibilities.extend(operand_incompatibilities);
+            }
+            incompatibilities
+        }
+        Expression::And(operands) => operands
+            .iter()
+            .flat_map(|operand| find_incompatibilities(project_license, operand))
+            .collect(),
+    }
+}
+
+struct DependencyCheck<'a> {
+    name: String,
+    version: String,
+    license: String,
+    incompatibilities: Vec<(String, &'a str)>,
+}
+
+pub struct CompatibilityReport<'a> {
+    project_license: &'a License,
+    dependencies: Vec<DependencyCheck<'a>>,
+    // Dependencies without an SPDX expression, whose compatibility is unknown.
+    unchecked_dependencies: Vec<DependencyCheck<'a>>,
+}
+
+impl CompatibilityReport<'_> {
+    pub fn is_compatible(&self) -> bool {
+        self.dependencies
+            .iter()
+            .all(|dependency| dependency.incompatibilities.is_empty())
+    }
+
+    pub fn render(&self) -> String {
+        let mut rendered_report = format!("Project license: {}\n", self.project_license.spdx_id());
+        let incompatible_dependencies: Vec<&DependencyCheck> = self
+            .dependencies
+            .iter()
+            .filter(|dependency| !dependency.incompatibilities.is_empty())
+            .collect();
+        let mut render_section = |title: &str, dependencies: &[&DependencyCheck]| {
+            if dependencies.is_empty() {
+                return;
+            }
+            rendered_report.push_str(&format!("{} ({})\n", title, dependencies.len()));
+            for dependency in dependencies {
+                rendered_report.push_str(&format!(
+                    "    {: <32}{: <16}{}\n",
+                    dependency.name, dependency.version, dependency.license
+                ));
+                for (identifier, reason) in &dependency.incompatibilities {
+                    rendered_report.push_str(&format!("        {}: {}\n", identifier, reason));
+                }
+            }
+        };
+        render_section("Incompatible", &incompatible_dependencies);
+        render_section(
+            "Not checked",
+            &self.unchecked_dependencies.iter().collect::<Vec<_>>(),
+        );
+        if incompatible_dependencies.is_empty() {
+            rendered_report.push_str(&format!(
+                "No known incompatibility with the licenses of {} dependencies\n",
+                self.dependencies.len()
+            ));
+        } else {
+            rendered_report.push_str(&format!(
+                "{} of {} dependencies are incompatible with the project license\n",
+                incompatible_dependencies.len(),
+                self.dependencies.len() + self.unchecked_dependencies.len()
+            ));
+        }
+        rendered_report
+    }
+}
+
+pub fn check_compatibility<'a>(
+    dependencies: &[Dependency],
+    project_license: &'a License,
+) -> CompatibilityReport<'a> {
+    let mut checked_dependencies = Vec::new();
+    let mut unchecked_dependencies = Vec::new();
+    for dependency in dependencies {
+        let parsed_expression_option = match dependency.declared_license() {
+            Some(DeclaredLicense::Expression(expression)) => parse_expression(expression).ok(),
+            _ => None,
+        };
+        let mut dependency_check = DependencyCheck {
+            name: dependency.name().to_string(),
+            version: dependency.version().to_string(),
+            license: dependency.render_license(),
+            incompatibilities: Vec::new(),
+        };
+        if let Some(parsed_expression) = parsed_expression_option {
+            dependency_check.incompatibilities =
+                find_incompatibilities(project_license, &parsed_expression);
+            checked_dependencies.push(dependency_check);
+        } else {
+            unchecked_dependencies.push(dependency_check);
+        }
+    }
+    CompatibilityReport {
+        project_license,
+        dependencies: checked_dependencies,
+        unchecked_dependencies,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Licenses;
+
+    fn find_incompatible_identifiers(project_license: &License, expression: &str) -> Vec<String> {
+        find_incompatibilities(project_license, &parse_expression(expression).unwrap())
+            .into_iter()
+            .map(|(identifier, _)| identifier)
+            .collect()
+    }
+
+    #[test]
+    fn find_incompatibilities_with_project_license() {
+        let licenses = Licenses::from_description_file();
+        let mit_license = licenses.find_license("mit").unwrap();
+        let gpl_2_0_license = licenses.find_license("gpl-2.0").unwrap();
+        assert!(find_incompatible_identifiers(mit_license, "MIT OR Apache-2.0").is_empty());
+        assert!(find_incompatible_identifiers(mit_license, "MIT OR GPL-3.0-only").is_empty());
+        assert_eq!(
+            find_incompatible_identifiers(mit_license, "MIT AND GPL-3.0-or-later"),
+            vec!["GPL-3.0-or-later"]
+        );
+        assert_eq!(
+            find_incompatible_identifiers(gpl_2_0_license, "Apache-2.0 OR GPL-3.0-only"),
+            vec!["Apache-2.0", "GPL-3.0-only"]
+        );
+        assert!(find_incompatible_identifiers(gpl_2_0_license, "MIT OR Apache-2.0").is_empty());
+        assert_eq!(
+            find_incompatible_identifiers(
+                gpl_2_0_license,
+                "Apache-2.0 WITH LLVM-exception AND MIT"
+            ),
+            vec!["Apache-2.0"]
+        );
+    }
+
+    #[test]
+    fn find_incompatibilities_with_deprecated_identifiers() {
+        let licenses = Licenses::from_description_file();
+        let mit_license = licenses.find_license("mit").unwrap();
+        let agpl_3_0_license = licenses.find_license("agpl-3.0").unwrap();
+        assert_eq!(
+            find_incompatible_identifiers(mit_license, "GPL-3.0+ AND GPL-3.0 AND AGPL-3.0"),
+            vec!["GPL-3.0+", "GPL-3.0", "AGPL-3.0"]
+        );
+        assert_eq!(
+            find_incompatible_identifiers(agpl_3_0_license, "LGPL-2.1 AND GPL-2.0"),
+            vec!["LGPL-2.1", "GPL-2.0"]
+        );
+        // Unlike version 2 only, version 2 or later can be distributed under version 3.
+        assert!(
+            find_incompatible_identifiers(agpl_3_0_license, "LGPL-2.1+ AND GPL-2.0+").is_empty()
+        );
+    }
+}
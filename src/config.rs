@@ -0,0 +1,257 @@
+use chrono::Datelike;
+use globset::Glob;
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::headers::HeaderStyle;
+use crate::policy::Policy;
+
+pub const PROJECT_CONFIG_FILE_BASENAME: &str = ".licensit.toml";
+const DEFAULT_LICENSE_FILE_BASENAME: &str = "LICENSE";
+const CURRENT_YEAR_POLICY: &str = "current";
+
+// Either a fixed year, so that the license file is the same whenever it is generated, or
+// `"current"` for the year it is generated in.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum YearPolicy {
+    Fixed(u32),
+    Named(String),
+}
+
+// The settings of a project, read from `.licensit.toml` at its root. Command-line options take
+// precedence over them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProjectConfig {
+    // Directory containing the configuration file, to which `exclude` is relative.
+    #[serde(skip)]
+    root: PathBuf,
+    license: Option<String>,
+    #[serde(default)]
+    holders: Vec<String>,
+    year: Option<YearPolicy>,
+    output: Option<PathBuf>,
+    header_style: Option<HeaderStyle>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    // The license policy of the dependencies, checked by `policy check`.
+    policy: Option<Policy>,
+}
+
+// Rewrites a glob relative to the directory of `.licensit.toml` to be relative to one of its
+// subdirectories, `None` if it can't match anything in it. As in `.gitignore`, globs without a
+// slash, except at their end, match at any depth and are kept as is.
+fn reroot_exclude_glob(glob: &str, relative_dir: &Path) -> Option<String> {
+    if !glob.trim_end_matches('/').contains('/') {
+        return Some(glob.to_string());
+    }
+    let mut segments = glob.trim_start_matches('/').split('/').peekable();
+    for component in relative_dir.components() {
+        let component = component.as_os_str().to_string_lossy();
+        match segments.peek() {
+            Some(&"**") => break,
+            Some(segment)
+                if Glob::new(segment).is_ok_and(|segment_glob| {
+                    segment_glob.compile_matcher().is_match(&*component)
+                }) =>
+            {
+                segments.next();
+            }
+            _ => return None,
+        }
+    }
+    let remaining_segments: Vec<&str> = segments.collect();
+    if remaining_segments.iter().all(|segment| segment.is_empty()) {
+        return None;
+    }
+    Some(format!("/{}", remaining_segments.join("/")))
+}
+
+impl ProjectConfig {
+    // Reads the first `.licensit.toml` found in the directory or its ancestors, and returns the
+    // default configuration, rooted at the directory, if there is none.
+    pub fn discover(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let absolute_dir = fs::canonicalize(dir)?;
+        let config_path_option = absolute_dir
+            .ancestors()
+            .map(|ancestor| ancestor.join(PROJECT_CONFIG_FILE_BASENAME))
+            .find(|config_path| config_path.is_file());
+        let Some(config_path) = config_path_option else {
+            return Ok(ProjectConfig {
+                root: dir.to_path_buf(),
+                ..ProjectConfig::default()
+            });
+        };
+        let config_content = fs::read_to_string(&config_path)
+            .map_err(|error| format!("{}: {}", config_path.display(), error))?;
+        let mut config: ProjectConfig = toml::from_str(&config_content)
+            .map_err(|error| format!("{}: {}", config_path.display(), error))?;
+        if let Some(YearPolicy::Named(year_policy)) = &config.year {
+            if year_policy != CURRENT_YEAR_POLICY {
+                return Err(format!(
+                    "{}: invalid year `{}`, expected a year or \"{}\"",
+                    config_path.display(),
+                    year_policy,
+                    CURRENT_YEAR_POLICY
+                )
+                .into());
+            }
+        }
+        config.root = config_path.parent().unwrap().to_path_buf();
+        Ok(config)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    // The holders joined into the single name that fills the license template.
+    pub fn holders(&self) -> Option<String> {
+        (!self.holders.is_empty()).then(|| self.holders.join(", "))
+    }
+
+    pub fn year(&self) -> u32 {
+        match self.year {
+            Some(YearPolicy::Fixed(year)) => year,
+            _ => chrono::Utc::now().year() as u32,
+        }
+    }
+
+    // Relative to the current directory, like the license file of a project without configuration.
+    pub fn license_file_path(&self) -> &Path {
+        self.output
+            .as_deref()
+            .unwrap_or(Path::new(DEFAULT_LICENSE_FILE_BASENAME))
+    }
+
+    pub fn header_style(&self) -> Option<HeaderStyle> {
+        self.header_style
+    }
+
+    pub fn exclude(&self) -> &Vec<String> {
+        &self.exclude
+    }
+
+    pub fn policy(&self) -> Option<&Policy> {
+        self.policy.as_ref()
+    }
+
+    // The globs of `exclude`, re-rooted from the directory of the configuration file to the scanned
+    // one, followed by the ones given on the command line.
+    pub fn combine_exclude_globs(
+        &self,
+        path: &Path,
+        exclude_globs: &[String],
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let scanned_dir = fs::canonicalize(path)?;
+        let relative_dir = scanned_dir
+            .strip_prefix(self.root())
+            .unwrap_or(Path::new(""));
+        Ok(self
+            .exclude()
+            .iter()
+            .filter_map(|glob| reroot_exclude_glob(glob, relative_dir))
+            .chain(exclude_globs.iter().cloned())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discover_config_in_ancestor_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join(PROJECT_CONFIG_FILE_BASENAME),
+            "\
+license = \"mit\"
+holders = [\"Jane Doe\", \"Example Corp\"]
+year = 2021
+output = \"LICENSE.txt\"
+header-style = \"license-and-copyright\"
+exclude = [\"vendor/**\"]
+
+[policy]
+allow = [\"MIT\"]
+",
+        )
+        .unwrap();
+        let nested_dir = temp_dir.path().join("src").join("bin");
+        fs::create_dir_all(&nested_dir).unwrap();
+        let config = ProjectConfig::discover(&nested_dir).unwrap();
+        let root = fs::canonicalize(temp_dir.path()).unwrap();
+        assert_eq!(config.root(), root);
+        assert_eq!(config.license(), Some("mit"));
+        assert_eq!(config.holders().as_deref(), Some("Jane Doe, Example Corp"));
+        assert_eq!(config.year(), 2021);
+        assert_eq!(config.license_file_path(), Path::new("LICENSE.txt"));
+        assert_eq!(
+            config.header_style(),
+            Some(HeaderStyle::LicenseAndCopyright)
+        );
+        assert_eq!(config.exclude(), &vec!["vendor/**".to_string()]);
+        assert!(config.policy().is_some());
+    }
+
+    #[test]
+    fn discover_without_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = ProjectConfig::discover(temp_dir.path()).unwrap();
+        assert_eq!(config.license(), None);
+        assert_eq!(config.holders(), None);
+        assert_eq!(config.year(), chrono::Utc::now().year() as u32);
+        assert_eq!(config.license_file_path(), Path::new("LICENSE"));
+    }
+
+    #[test]
+    fn discover_config_with_invalid_values() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join(PROJECT_CONFIG_FILE_BASENAME);
+        fs::write(&config_path, "year = \"last\"\n").unwrap();
+        assert!(ProjectConfig::discover(temp_dir.path()).is_err());
+        fs::write(&config_path, "year = \"current\"\n").unwrap();
+        assert!(ProjectConfig::discover(temp_dir.path()).is_ok());
+        fs::write(&config_path, "licence = \"mit\"\n").unwrap();
+        assert!(ProjectConfig::discover(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn reroot_exclude_globs_to_subdirectory() {
+        let relative_dir = Path::new("src");
+        assert_eq!(
+            reroot_exclude_glob("src/gen/**", relative_dir).as_deref(),
+            Some("/gen/**")
+        );
+        assert_eq!(
+            reroot_exclude_glob("/s*/lib.rs", relative_dir).as_deref(),
+            Some("/lib.rs")
+        );
+        assert_eq!(
+            reroot_exclude_glob("**/gen/", relative_dir).as_deref(),
+            Some("/**/gen/")
+        );
+        assert_eq!(
+            reroot_exclude_glob("*.pb.rs", relative_dir).as_deref(),
+            Some("*.pb.rs")
+        );
+        assert_eq!(reroot_exclude_glob("vendor/**", relative_dir), None);
+        assert_eq!(
+            reroot_exclude_glob("src/", relative_dir).as_deref(),
+            Some("src/")
+        );
+        assert_eq!(reroot_exclude_glob("/src", relative_dir), None);
+        assert_eq!(
+            reroot_exclude_glob("src/gen/**", Path::new("")).as_deref(),
+            Some("/src/gen/**")
+        );
+    }
+}
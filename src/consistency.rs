@@ -0,0 +1,226 @@
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::Licenses;
+use crate::manifests::{find_manifests, read_manifest_license, DeclaredLicense};
+use crate::reuse::LICENSE_FILE_BASENAME_PREFIXES;
+use crate::spdx::find_expression_identifiers;
+use crate::walk::display_relative_path;
+
+// `LICENSE`, `LICENSE-MIT`, `COPYING.txt` and so on in the root of the project.
+pub fn find_license_files(root: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut license_files = Vec::new();
+    for entry_result in fs::read_dir(root)? {
+        let entry_path = entry_result?.path();
+        let file_name = entry_path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_uppercase())
+            .unwrap_or_default();
+        let is_license_file = LICENSE_FILE_BASENAME_PREFIXES
+            .iter()
+            .any(|prefix| file_name.starts_with(prefix));
+        if is_license_file && entry_path.is_file() {
+            license_files.push(entry_path);
+        }
+    }
+    license_files.sort();
+    Ok(license_files)
+}
+
+// Identifiers of catalog licenses are replaced by the catalog's own, so that `GPL-3.0-or-later`
+// matches the detected `GPL-3.0-only` text.
+pub fn normalize_license_identifier(licenses: &Licenses, identifier: &str) -> String {
+    licenses
+        .find_license_by_spdx_id(identifier)
+        .map_or(identifier.to_string(), |license| {
+            license.spdx_id().to_string()
+        })
+}
+
+struct ManifestCheck {
+    path: PathBuf,
+    declared_license: Option<DeclaredLicense>,
+    agrees: bool,
+}
+
+pub struct ConsistencyReport {
+    // The SPDX identifier of the detected license of each license file.
+    license_files: Vec<(PathBuf, Option<String>)>,
+    manifests: Vec<ManifestCheck>,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        !self.license_files.is_empty() && self.manifests.iter().all(|manifest| manifest.agrees)
+    }
+
+    pub fn render(&self, root: &Path) -> String {
+        let mut rendered_report = String::new();
+        if self.license_files.is_empty() {
+            rendered_report.push_str("No license file found\n");
+        } else {
+            rendered_report.push_str("License files:\n");
+            for (path, detected_license_option) in &self.license_files {
+                rendered_report.push_str(&format!(
+                    "    {}: {}\n",
+                    display_relative_path(root, path),
+                    detected_license_option
+                        .as_deref()
+                        .unwrap_or("unrecognized license")
+                ));
+            }
+        }
+        if self.manifests.is_empty() {
+            rendered_report.push_str("No package manifest found\n");
+        } else {
+            rendered_report.push_str("Package manifests:\n");
+            for manifest in &self.manifests {
+                let rendered_declared_license = match &manifest.declared_license {
+                    Some(DeclaredLicense::Expression(expression)) => expression.to_string(),
+                    Some(DeclaredLicense::File(file)) => format!("license file {}", file),
+                    None => "no license".to_string(),
+                };
+                rendered_report.push_str(&format!(
+                    "    {}: {}{}\n",
+                    display_relative_path(root, &manifest.path),
+                    rendered_declared_license,
+                    if manifest.agrees { "" } else { " (disagrees)" }
+                ));
+            }
+        }
+        if !self.is_consistent() {
+            rendered_report
+                .push_str("The license files and the package manifests are inconsistent\n");
+        }
+        rendered_report
+    }
+}
+
+// A manifest agrees if it declares exactly the licenses detected in the license files, or if it
+// references a license file that exists.
+pub fn check_consistency(
+    root: &Path,
+    licenses: &Licenses,
+) -> Result<ConsistencyReport, Box<dyn Error>> {
+    let mut license_files = Vec::new();
+    for license_file in find_license_files(root)? {
+        let license_text = String::from_utf8_lossy(&fs::read(&license_file)?).to_string();
+        let detected_license_option = licenses
+            .detect_license(&license_text)
+            .map(|license| license.spdx_id().to_string());
+        license_files.push((license_file, detected_license_option));
+    }
+    let detected_license_identifiers: BTreeSet<String> = license_files
+        .iter()
+        .filter_map(|(_, detected_license_option)| detected_license_option.clone())
+        .collect();
+    let mut manifests = Vec::new();
+    for (manifest_kind, manifest_path) in find_manifests(root)? {
+        let declared_license = read_manifest_license(manifest_kind, &manifest_path, licenses)?;
+        let agrees = match &declared_license {
+            Some(DeclaredLicense::Expression(expression)) => {
+                let declared_license_identifiers: BTreeSet<String> =
+                    find_expression_identifiers(expression)
+                        .iter()
+                        .map(|identifier| normalize_license_identifier(licenses, identifier))
+                        .collect();
+                declared_license_identifiers == detected_license_identifiers
+            }
+            Some(DeclaredLicense::File(file)) => root.join(file).is_file(),
+            None => false,
+        };
+        manifests.push(ManifestCheck {
+            path: manifest_path,
+            declared_license,
+            agrees,
+        });
+    }
+    Ok(ConsistencyReport {
+        license_files,
+        manifests,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_project_files;
+
+    #[test]
+    fn check_consistency_of_dual_licensed_project() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                (
+                    "LICENSE-MIT",
+                    licenses.find_license("mit").unwrap().fetch_template(),
+                ),
+                (
+                    "LICENSE-APACHE",
+                    licenses
+                        .find_license("apache-2.0")
+                        .unwrap()
+                        .fetch_template(),
+                ),
+                (
+                    "Cargo.toml",
+                    "[package]\nname = \"crate\"\nlicense = \"MIT OR Apache-2.0\"\n",
+                ),
+            ],
+        );
+        let consistency_report = check_consistency(temp_dir.path(), &licenses).unwrap();
+        assert!(consistency_report.is_consistent());
+        assert_eq!(
+            consistency_report.render(temp_dir.path()),
+            "\
+License files:
+    LICENSE-APACHE: Apache-2.0
+    LICENSE-MIT: MIT
+Package manifests:
+    Cargo.toml: MIT OR Apache-2.0
+"
+        );
+    }
+
+    #[test]
+    fn check_consistency_with_disagreeing_manifests() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                (
+                    "LICENSE",
+                    licenses.find_license("gpl-3.0").unwrap().fetch_template(),
+                ),
+                (
+                    "pyproject.toml",
+                    "[project]\nname = \"package\"\nlicense = \"GPL-3.0-or-later\"\n",
+                ),
+                (
+                    "package.json",
+                    "{\"name\": \"package\", \"license\": \"MIT\"}",
+                ),
+                ("composer.json", "{\"name\": \"vendor/package\"}"),
+            ],
+        );
+        let consistency_report = check_consistency(temp_dir.path(), &licenses).unwrap();
+        assert!(!consistency_report.is_consistent());
+        assert_eq!(
+            consistency_report.render(temp_dir.path()),
+            "\
+License files:
+    LICENSE: GPL-3.0-only
+Package manifests:
+    package.json: MIT (disagrees)
+    pyproject.toml: GPL-3.0-or-later
+    composer.json: no license (disagrees)
+The license files and the package manifests are inconsistent
+"
+        );
+    }
+}
@@ -1,63 +1,345 @@
 use clap::Parser;
 use colored::Colorize;
 use include_dir::{include_dir, Dir};
-use serde::Deserialize;
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
 
-use crate::cli::{Cli, Commands, LICENSE_ARG};
+use crate::annotations::{append_dep5_paragraph, append_reuse_toml_annotation};
+use crate::choose::choose_license;
+use crate::cli::{
+    determine_license_author, Cli, Commands, ListFilters, ListLayout, PolicyCommands,
+    ReuseCommands, LICENSE_ARG,
+};
+use crate::compare::{render_comparison, render_word_diff};
+use crate::compatibility::check_compatibility;
+use crate::config::{ProjectConfig, PROJECT_CONFIG_FILE_BASENAME};
+use crate::consistency::{check_consistency, find_license_files};
+use crate::debian::{render_debian_copyright, DEBIAN_COPYRIGHT_FILE_PATH};
+use crate::deps::{collect_dependencies, find_cargo_home, render_dependencies_by_license};
+use crate::detect::{compute_similarity, find_word_bigrams, MIN_LICENSE_TEXT_SIMILARITY};
+use crate::headers::find_files_without_header;
+use crate::manifests::{sync_manifests, ManifestLicense};
+use crate::metadata::{Condition, Copyleft, Limitation, Permission};
+use crate::notices::render_notices;
+use crate::output::{
+    render_structured_output, LicenseDescription, LicenseIdentifiers, LicenseSummary, OutputFormat,
+    PlaceholderValue, RenderedLicense,
+};
+use crate::policy::{check_policy, Policy};
+use crate::reuse::{find_used_license_identifiers, lint, write_license_text};
+use crate::sbom::SbomDocument;
+use crate::search::search_templates;
+use crate::spdx::{canonicalize_license_identifier, strip_license_variant};
+use crate::table::{fetch_terminal_width, render_table, ListColumn};
+use crate::walk::display_relative_path;
 
 static LICENSES_DATA_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/data/licenses");
 const LICENSES_DESCRIPTIONS_FILE_BASENAME: &str = "licenses.yml";
 
 #[derive(Debug, Deserialize)]
-struct Placeholders {
+pub struct Placeholders {
     author: String,
     year: String,
 }
 
 impl Placeholders {
-    fn author(&self) -> &str {
+    pub fn author(&self) -> &str {
         &self.author
     }
 
-    fn year(&self) -> &str {
+    pub fn year(&self) -> &str {
         &self.year
     }
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MavenLicense {
+    name: String,
+    url: String,
+}
+
+impl MavenLicense {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LicenseIds {
+    pypi: String,
+    debian: String,
+    fedora: String,
+    maven: MavenLicense,
+}
+
+impl LicenseIds {
+    pub fn pypi(&self) -> &str {
+        &self.pypi
+    }
+
+    // Short name of the `debian/copyright` format.
+    pub fn debian(&self) -> &str {
+        &self.debian
+    }
+
+    // Short name used in the `License:` tag of RPM spec files before Fedora adopted SPDX.
+    pub fn fedora(&self) -> &str {
+        &self.fedora
+    }
+
+    pub fn maven(&self) -> &MavenLicense {
+        &self.maven
+    }
+}
+
+// Licenses of dependencies whose code can't be included in a project under the license.
 #[derive(Debug, Deserialize)]
-struct License {
+pub struct Incompatibility {
+    spdx_ids: Vec<String>,
+    reason: String,
+}
+
+impl Incompatibility {
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct License {
     name: String,
+    spdx_id: String,
     full_name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    // Identified by a deprecated SPDX identifier, such as `GPL-2.0`, superseded by more precise ones.
+    #[serde(default)]
+    deprecated: bool,
+    osi_approved: bool,
+    fsf_libre: bool,
+    permissions: Vec<Permission>,
+    conditions: Vec<Condition>,
+    // Overrides the scope derived from the conditions, where it is narrower than they suggest.
+    copyleft: Option<Copyleft>,
+    limitations: Vec<Limitation>,
+    // Plain-language overview, not legal advice.
+    summary: String,
     template: String,
     placeholders: Option<Placeholders>,
+    ids: LicenseIds,
+    #[serde(default)]
+    incompatible_dependencies: Vec<Incompatibility>,
 }
 
 impl License {
-    fn name(&self) -> &str {
+    pub fn name(&self) -> &str {
         &self.name
     }
 
-    fn full_name(&self) -> &str {
+    pub fn spdx_id(&self) -> &str {
+        &self.spdx_id
+    }
+
+    pub fn full_name(&self) -> &str {
         &self.full_name
     }
 
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    pub fn deprecated(&self) -> bool {
+        self.deprecated
+    }
+
+    // Approved by the Open Source Initiative.
+    pub fn osi_approved(&self) -> bool {
+        self.osi_approved
+    }
+
+    // Free according to the Free Software Foundation.
+    pub fn fsf_libre(&self) -> bool {
+        self.fsf_libre
+    }
+
+    pub fn permissions(&self) -> &Vec<Permission> {
+        &self.permissions
+    }
+
+    pub fn conditions(&self) -> &Vec<Condition> {
+        &self.conditions
+    }
+
+    pub fn limitations(&self) -> &Vec<Limitation> {
+        &self.limitations
+    }
+
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    pub fn copyleft(&self) -> Copyleft {
+        self.copyleft
+            .unwrap_or_else(|| Copyleft::from_conditions(self.conditions()))
+    }
+
+    fn matches_search(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        [self.name(), self.spdx_id(), self.full_name()]
+            .into_iter()
+            .chain(self.aliases().iter().map(String::as_str))
+            .any(|value| value.to_lowercase().contains(&text))
+    }
+
+    fn matches_filters(&self, filters: &ListFilters) -> bool {
+        let copyleft_option = if filters.is_permissive {
+            Some(Copyleft::None)
+        } else {
+            filters.copyleft
+        };
+        (!filters.is_osi_approved || self.osi_approved())
+            && (!filters.is_fsf_libre || self.fsf_libre())
+            && copyleft_option.map_or(true, |copyleft| self.copyleft() == copyleft)
+            && (!filters.is_deprecated || self.deprecated())
+            && filters
+                .search
+                .as_deref()
+                .map_or(true, |text| self.matches_search(text))
+    }
+
     fn template(&self) -> &str {
         &self.template
     }
 
-    fn placeholders(&self) -> &Option<Placeholders> {
+    pub fn placeholders(&self) -> &Option<Placeholders> {
         &self.placeholders
     }
 
-    fn fetch_template(&self) -> &str {
+    pub fn ids(&self) -> &LicenseIds {
+        &self.ids
+    }
+
+    fn incompatible_dependencies(&self) -> &Vec<Incompatibility> {
+        &self.incompatible_dependencies
+    }
+
+    // Deprecated identifiers, such as `GPL-3.0` or `GPL-2.0+`, match their `-only` and `-or-later`
+    // forms, which are still told apart.
+    pub fn find_incompatibility(&self, spdx_id: &str) -> Option<&Incompatibility> {
+        let canonical_spdx_id = canonicalize_license_identifier(spdx_id);
+        self.incompatible_dependencies()
+            .iter()
+            .find(|incompatibility| {
+                incompatibility.spdx_ids.iter().any(|incompatible_spdx_id| {
+                    canonicalize_license_identifier(incompatible_spdx_id) == canonical_spdx_id
+                })
+            })
+    }
+
+    // The GNU licenses share the same text for their `-only` and `-or-later` variants.
+    fn matches_spdx_id(&self, spdx_id: &str) -> bool {
+        strip_license_variant(self.spdx_id()) == strip_license_variant(spdx_id)
+    }
+
+    pub fn fetch_template(&self) -> &str {
         let template_relative_path = self.template();
         let template_file = LICENSES_DATA_DIR.get_file(template_relative_path).unwrap();
         template_file.contents_utf8().unwrap()
     }
 
+    fn render_ids(&self) -> String {
+        let ids = self.ids();
+        let maven_license = ids.maven();
+        [
+            ("SPDX", self.spdx_id().to_string()),
+            ("PyPI", ids.pypi().to_string()),
+            ("Debian", ids.debian().to_string()),
+            ("Fedora", ids.fedora().to_string()),
+            ("Maven", maven_license.name().to_string()),
+            ("Maven URL", maven_license.url().to_string()),
+        ]
+        .iter()
+        .map(|(ecosystem, id)| format!("{: <16}{}\n", ecosystem, id))
+        .collect()
+    }
+
+    // The summary wrapped to 80 columns, followed by a reminder that it is no substitute for the
+    // license text.
+    fn render_summary(&self) -> String {
+        let mut rendered_summary = format!("{}\n\n", self.full_name());
+        let mut line = String::new();
+        for word in self.summary().split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > 80 {
+                rendered_summary.push_str(&format!("{}\n", line));
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        rendered_summary.push_str(&format!("{}\n", line));
+        rendered_summary.push_str(&format!(
+            "\nNot legal advice, see `licensit show {}` for the license text.\n",
+            self.name()
+        ));
+        rendered_summary
+    }
+
+    fn render_info(&self) -> String {
+        let render_yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+        let mut rendered_info: String = [
+            ("Name", self.name().to_string()),
+            ("SPDX", self.spdx_id().to_string()),
+            ("Full name", self.full_name().to_string()),
+            ("OSI approved", render_yes_no(self.osi_approved())),
+            ("FSF libre", render_yes_no(self.fsf_libre())),
+        ]
+        .iter()
+        .map(|(field, value)| format!("{: <16}{}\n", field, value))
+        .collect();
+        for (title, labels) in [
+            (
+                "Permissions",
+                self.permissions()
+                    .iter()
+                    .map(|permission| permission.label())
+                    .collect::<Vec<&str>>(),
+            ),
+            (
+                "Conditions",
+                self.conditions()
+                    .iter()
+                    .map(|condition| condition.label())
+                    .collect(),
+            ),
+            (
+                "Limitations",
+                self.limitations()
+                    .iter()
+                    .map(|limitation| limitation.label())
+                    .collect(),
+            ),
+        ] {
+            rendered_info.push_str(&format!("\n{}\n", title));
+            if labels.is_empty() {
+                rendered_info.push_str("    None\n");
+            }
+            for label in labels {
+                rendered_info.push_str(&format!("    {}\n", label));
+            }
+        }
+        rendered_info
+    }
+
     fn render_licence(&self, author: &str, year: &u32) -> String {
         let template = self.fetch_template();
         let placeholders_option = self.placeholders();
@@ -72,12 +354,12 @@ impl License {
 }
 
 #[derive(Deserialize)]
-struct Licenses {
+pub struct Licenses {
     licenses: Vec<License>,
 }
 
 impl Licenses {
-    fn from_description_file() -> Self {
+    pub fn from_description_file() -> Self {
         let description_file = LICENSES_DATA_DIR
             .get_file(LICENSES_DESCRIPTIONS_FILE_BASENAME)
             .unwrap();
@@ -85,37 +367,106 @@ impl Licenses {
         serde_yaml::from_str::<Licenses>(description_file_content).unwrap()
     }
 
-    fn licenses(&self) -> &Vec<License> {
+    pub fn licenses(&self) -> &Vec<License> {
         &self.licenses
     }
 
-    fn find_license(&self, name: &str) -> Option<&License> {
+    pub fn find_license(&self, name: &str) -> Option<&License> {
         self.licenses()
             .iter()
             .find(|&license| license.name() == name)
     }
 
-    fn fetch_licenses_names(&self) -> Vec<String> {
+    pub fn find_license_by_spdx_id(&self, spdx_id: &str) -> Option<&License> {
         self.licenses()
             .iter()
-            .map(|license| license.name().to_string())
-            .collect()
+            .find(|&license| license.matches_spdx_id(spdx_id))
     }
 
-    fn fetch_licenses_full_names(&self) -> Vec<String> {
+    pub fn find_license_by_maven_name(&self, maven_name: &str) -> Option<&License> {
         self.licenses()
             .iter()
-            .map(|license| license.full_name().to_string())
-            .collect()
+            .find(|&license| license.ids().maven().name() == maven_name)
+    }
+
+    // Several licenses share some classifiers, such as `BSD License`, which then match none.
+    pub fn find_license_by_pypi_classifier(&self, classifier: &str) -> Option<&License> {
+        let mut matching_licenses = self
+            .licenses()
+            .iter()
+            .filter(|&license| license.ids().pypi() == classifier);
+        let license = matching_licenses.next()?;
+        matching_licenses.next().is_none().then_some(license)
+    }
+
+    // Returns the license whose template is the most similar to the text, if similar enough.
+    pub fn detect_license(&self, text: &str) -> Option<&License> {
+        let text_bigrams = find_word_bigrams(text);
+        self.licenses()
+            .iter()
+            .map(|license| {
+                let template_bigrams = find_word_bigrams(license.fetch_template());
+                (
+                    license,
+                    compute_similarity(&text_bigrams, &template_bigrams),
+                )
+            })
+            .filter(|(_, similarity)| *similarity >= MIN_LICENSE_TEXT_SIMILARITY)
+            .max_by(|(_, first_similarity), (_, second_similarity)| {
+                first_similarity.total_cmp(second_similarity)
+            })
+            .map(|(license, _)| license)
     }
 
-    fn fetch_formatted_licenses_names_and_full_names(&self) -> Vec<String> {
-        self.fetch_licenses_names()
+    fn fetch_licenses_names(&self) -> Vec<String> {
+        self.licenses()
             .iter()
-            .zip(&self.fetch_licenses_full_names())
-            .map(|(name, full_name)| format!("{: <16}{}", name, full_name))
+            .map(|license| license.name().to_string())
             .collect()
     }
+
+    fn select_licenses(&self, filters: &ListFilters, layout: &ListLayout) -> Vec<&License> {
+        let mut selected_licenses: Vec<&License> = self
+            .licenses()
+            .iter()
+            .filter(|&license| license.matches_filters(filters))
+            .collect();
+        layout.sort.sort(&mut selected_licenses);
+        if layout.is_reverse {
+            selected_licenses.reverse();
+        }
+        selected_licenses
+    }
+
+    fn render_licenses_table(
+        &self,
+        filters: &ListFilters,
+        layout: &ListLayout,
+        max_width: Option<usize>,
+    ) -> String {
+        let columns = layout
+            .columns
+            .as_deref()
+            .unwrap_or(&[ListColumn::Id, ListColumn::Name]);
+        let mut rows = Vec::new();
+        if layout.columns.is_some() {
+            rows.push(
+                columns
+                    .iter()
+                    .map(|column| column.header().to_string())
+                    .collect(),
+            );
+        }
+        for license in self.select_licenses(filters, layout) {
+            rows.push(
+                columns
+                    .iter()
+                    .map(|column| column.render(license))
+                    .collect(),
+            );
+        }
+        render_table(&rows, max_width)
+    }
 }
 
 fn render_invalid_value_error_message(invalid_arg: &str, possible_values: &[String]) -> String {
@@ -133,31 +484,137 @@ fn render_invalid_value_error_message(invalid_arg: &str, possible_values: &[Stri
     )
 }
 
-fn render_nonexistent_license_error(licenses: &Licenses) -> String {
+pub fn render_nonexistent_license_error(licenses: &Licenses) -> String {
     let licenses_names = licenses.fetch_licenses_names();
     render_invalid_value_error_message(LICENSE_ARG, &licenses_names)
 }
 
+fn add_license(
+    license: &License,
+    author: &str,
+    year: &u32,
+    config: &ProjectConfig,
+    is_manifest_synced: bool,
+) -> Result<(), Box<dyn Error>> {
+    let rendered_license = license.render_licence(author, year);
+    let mut rendered_license_file = File::create(config.license_file_path())?;
+    rendered_license_file.write_all(rendered_license.as_bytes())?;
+    if is_manifest_synced {
+        sync_project_manifests(Path::new("."), &ManifestLicense::Catalog(license))?;
+    }
+    Ok(())
+}
+
+fn sync_project_manifests(
+    root: &Path,
+    manifest_license: &ManifestLicense,
+) -> Result<(), Box<dyn Error>> {
+    for updated_manifest_path in sync_manifests(root, manifest_license)? {
+        println!(
+            "Updated {}",
+            display_relative_path(root, &updated_manifest_path)
+        );
+    }
+    Ok(())
+}
+
+// The license given by name, or the first one recognized in the license files of the project.
+fn select_project_license<'a>(
+    root: &Path,
+    name: Option<&str>,
+    licenses: &'a Licenses,
+) -> Result<&'a License, Box<dyn Error>> {
+    if let Some(name) = name {
+        return Ok(licenses.find_license(name).unwrap_or_else(|| {
+            // This error scenario can also be handled using `clap`.
+            let nonexistent_license_error = render_nonexistent_license_error(licenses);
+            eprintln!("{}", nonexistent_license_error);
+            std::process::exit(2);
+        }));
+    }
+    for license_file in find_license_files(root)? {
+        let license_text = String::from_utf8_lossy(&fs::read(license_file)?).to_string();
+        if let Some(license) = licenses.detect_license(&license_text) {
+            return Ok(license);
+        }
+    }
+    Err("no license given and none recognized in the license files".into())
+}
+
 pub fn run() -> Result<(), Box<dyn Error>> {
     let licenses = Licenses::from_description_file();
     let cli = Cli::parse();
+    let supports_structured_output = matches!(
+        cli.command,
+        Commands::List { .. } | Commands::Show { .. } | Commands::Info { .. }
+    );
+    if cli.format != OutputFormat::Text && !supports_structured_output {
+        return Err("--format is only supported by list, show and info".into());
+    }
     match &cli.command {
-        Commands::List => {
-            let formatted_licenses_names_and_full_names =
-                licenses.fetch_formatted_licenses_names_and_full_names();
-            for formatted_license_name_and_full_name in formatted_licenses_names_and_full_names {
-                println!("{}", formatted_license_name_and_full_name);
-            }
+        Commands::List { filters, layout } if cli.format != OutputFormat::Text => {
+            let license_descriptions: Vec<LicenseDescription> = licenses
+                .select_licenses(filters, layout)
+                .into_iter()
+                .map(LicenseDescription::new)
+                .collect();
+            print!(
+                "{}",
+                render_structured_output(&license_descriptions, cli.format)?
+            );
+        }
+        Commands::List { filters, layout } => {
+            print!(
+                "{}",
+                licenses.render_licenses_table(filters, layout, fetch_terminal_width())
+            );
         }
         Commands::Show {
             name,
             author,
             year,
             is_template,
+            is_ids,
+            is_summary,
         } => {
             let license_option = licenses.find_license(name);
             if let Some(license) = license_option {
-                if *is_template {
+                if cli.format != OutputFormat::Text {
+                    let structured_output = if *is_ids {
+                        render_structured_output(&LicenseIdentifiers::new(license), cli.format)?
+                    } else if *is_summary {
+                        render_structured_output(&LicenseSummary::new(license), cli.format)?
+                    } else {
+                        let text = if *is_template {
+                            license.fetch_template().to_string()
+                        } else {
+                            license.render_licence(author, year)
+                        };
+                        let mut placeholder_values = Vec::new();
+                        if let Some(placeholders) = license.placeholders() {
+                            let fill = |value: String| (!*is_template).then_some(value);
+                            placeholder_values.push(PlaceholderValue::new(
+                                "author",
+                                placeholders.author(),
+                                fill(author.to_string()),
+                            ));
+                            placeholder_values.push(PlaceholderValue::new(
+                                "year",
+                                placeholders.year(),
+                                fill(year.to_string()),
+                            ));
+                        }
+                        render_structured_output(
+                            &RenderedLicense::new(license, &text, placeholder_values),
+                            cli.format,
+                        )?
+                    };
+                    print!("{}", structured_output);
+                } else if *is_ids {
+                    print!("{}", license.render_ids());
+                } else if *is_summary {
+                    print!("{}", license.render_summary());
+                } else if *is_template {
                     let template = license.fetch_template();
                     print!("{}", template);
                 } else {
@@ -171,12 +628,124 @@ pub fn run() -> Result<(), Box<dyn Error>> {
                 std::process::exit(2);
             }
         }
-        Commands::Add { name, author, year } => {
+        Commands::Info { name } => {
+            if let Some(license) = licenses.find_license(name) {
+                if cli.format != OutputFormat::Text {
+                    let license_description = LicenseDescription::new(license);
+                    print!(
+                        "{}",
+                        render_structured_output(&license_description, cli.format)?
+                    );
+                } else {
+                    print!("{}", license.render_info());
+                }
+            } else {
+                // This error scenario can also be handled using `clap`.
+                let nonexistent_license_error = render_nonexistent_license_error(&licenses);
+                eprintln!("{}", nonexistent_license_error);
+                std::process::exit(2);
+            }
+        }
+        Commands::Compare { names, is_all } => {
+            let license_options: Option<Vec<&License>> = names
+                .iter()
+                .map(|name| licenses.find_license(name))
+                .collect();
+            if let Some(compared_licenses) = license_options {
+                print!("{}", render_comparison(&compared_licenses, *is_all));
+            } else {
+                // This error scenario can also be handled using `clap`.
+                let nonexistent_license_error = render_nonexistent_license_error(&licenses);
+                eprintln!("{}", nonexistent_license_error);
+                std::process::exit(2);
+            }
+        }
+        Commands::Diff { old_name, new_name } => {
+            if let (Some(old_license), Some(new_license)) = (
+                licenses.find_license(old_name),
+                licenses.find_license(new_name),
+            ) {
+                print!(
+                    "{}",
+                    render_word_diff(old_license.fetch_template(), new_license.fetch_template())
+                );
+            } else {
+                // This error scenario can also be handled using `clap`.
+                let nonexistent_license_error = render_nonexistent_license_error(&licenses);
+                eprintln!("{}", nonexistent_license_error);
+                std::process::exit(2);
+            }
+        }
+        Commands::Search {
+            pattern,
+            names,
+            is_case_insensitive,
+            context_lines_count,
+            is_list,
+        } => {
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(*is_case_insensitive)
+                .build()?;
+            let searched_licenses: Vec<&License> = if names.is_empty() {
+                licenses.licenses().iter().collect()
+            } else if let Some(searched_licenses) = names
+                .iter()
+                .map(|name| licenses.find_license(name))
+                .collect()
+            {
+                searched_licenses
+            } else {
+                // This error scenario can also be handled using `clap`.
+                let nonexistent_license_error = render_nonexistent_license_error(&licenses);
+                eprintln!("{}", nonexistent_license_error);
+                std::process::exit(2);
+            };
+            let licenses_matches = search_templates(&searched_licenses, &regex);
+            for (index, license_matches) in licenses_matches.iter().enumerate() {
+                if *is_list {
+                    let matches_count = license_matches.matches_count();
+                    println!(
+                        "{: <16}{} {}",
+                        license_matches.license().name(),
+                        matches_count,
+                        if matches_count == 1 {
+                            "match"
+                        } else {
+                            "matches"
+                        }
+                    );
+                } else {
+                    if index > 0 {
+                        println!();
+                    }
+                    print!("{}", license_matches.render(*context_lines_count));
+                }
+            }
+            if licenses_matches.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Add {
+            name,
+            author,
+            year,
+            is_manifest_synced,
+        } => {
+            let config = ProjectConfig::discover(Path::new("."))?;
+            let name = name.as_deref().or(config.license()).ok_or_else(|| {
+                format!(
+                    "no license given, and no license set in {}",
+                    PROJECT_CONFIG_FILE_BASENAME
+                )
+            })?;
             let license_option = licenses.find_license(name);
             if let Some(license) = license_option {
-                let rendered_license = license.render_licence(author, year);
-                let mut rendered_license_file = File::create("LICENSE")?;
-                rendered_license_file.write_all(rendered_license.as_bytes())?;
+                let author = author
+                    .clone()
+                    .or_else(|| config.holders())
+                    .unwrap_or_else(determine_license_author);
+                let year = year.unwrap_or_else(|| config.year());
+                add_license(license, &author, &year, &config, *is_manifest_synced)?;
             } else {
                 // This error scenario can also be handled using `clap`.
                 let nonexistent_license_error = render_nonexistent_license_error(&licenses);
@@ -184,6 +753,262 @@ pub fn run() -> Result<(), Box<dyn Error>> {
                 std::process::exit(2);
             }
         }
+        Commands::Choose {
+            author,
+            year,
+            is_manifest_synced,
+        } => {
+            let config = ProjectConfig::discover(Path::new("."))?;
+            let stdin = io::stdin();
+            let license_option = choose_license(&licenses, &mut stdin.lock(), &mut io::stdout())?;
+            if let Some(license) = license_option {
+                let author = author
+                    .clone()
+                    .or_else(|| config.holders())
+                    .unwrap_or_else(determine_license_author);
+                let year = year.unwrap_or_else(|| config.year());
+                add_license(license, &author, &year, &config, *is_manifest_synced)?;
+                println!(
+                    "Added {} to {}",
+                    license.full_name(),
+                    config.license_file_path().display()
+                );
+            }
+        }
+        Commands::SyncManifest {
+            name,
+            license_file,
+            expression,
+        } => {
+            let manifest_license = if let Some(license_file) = license_file {
+                ManifestLicense::File(license_file.to_string())
+            } else if let Some(expression) = expression {
+                ManifestLicense::Expression(expression.to_string())
+            } else if let Some(license) =
+                name.as_deref().and_then(|name| licenses.find_license(name))
+            {
+                ManifestLicense::Catalog(license)
+            } else {
+                // This error scenario can also be handled using `clap`.
+                let nonexistent_license_error = render_nonexistent_license_error(&licenses);
+                eprintln!("{}", nonexistent_license_error);
+                std::process::exit(2);
+            };
+            sync_project_manifests(Path::new("."), &manifest_license)?;
+        }
+        Commands::Consistency { path } => {
+            let consistency_report = check_consistency(path, &licenses)?;
+            print!("{}", consistency_report.render(path));
+            if !consistency_report.is_consistent() {
+                std::process::exit(1);
+            }
+        }
+        Commands::DebianCopyright {
+            name,
+            author,
+            year,
+            excludes,
+            use_cache,
+        } => {
+            let current_dir = Path::new(".");
+            let config = ProjectConfig::discover(current_dir)?;
+            let license = select_project_license(current_dir, name.as_deref(), &licenses)?;
+            let debian_copyright = render_debian_copyright(
+                current_dir,
+                &licenses,
+                license,
+                author,
+                *year,
+                &config.combine_exclude_globs(current_dir, excludes)?,
+                *use_cache,
+            )?;
+            let debian_copyright_path = current_dir.join(DEBIAN_COPYRIGHT_FILE_PATH);
+            fs::create_dir_all(debian_copyright_path.parent().unwrap())?;
+            fs::write(&debian_copyright_path, debian_copyright)?;
+            println!(
+                "Created {}",
+                display_relative_path(current_dir, &debian_copyright_path)
+            );
+        }
+        Commands::Deps { path } => {
+            let cargo_home_option = find_cargo_home();
+            let dependencies = collect_dependencies(path, cargo_home_option.as_deref(), &licenses)?;
+            print!("{}", render_dependencies_by_license(&dependencies));
+        }
+        Commands::Notices {
+            path,
+            notices_format,
+            output,
+        } => {
+            let cargo_home_option = find_cargo_home();
+            let dependencies = collect_dependencies(path, cargo_home_option.as_deref(), &licenses)?;
+            let (notices, dependencies_without_text) =
+                render_notices(&dependencies, &licenses, *notices_format)?;
+            for dependency_without_text in dependencies_without_text {
+                eprintln!(
+                    "{}: no license text found for '{}'",
+                    "warning".yellow(),
+                    dependency_without_text.bold()
+                );
+            }
+            let notices_path = output
+                .clone()
+                .unwrap_or_else(|| path.join(notices_format.default_file_name()));
+            fs::write(&notices_path, notices)?;
+            println!(
+                "Created {}",
+                display_relative_path(Path::new("."), &notices_path)
+            );
+        }
+        Commands::Sbom {
+            path,
+            sbom_format,
+            output,
+            embeds_license_texts,
+        } => {
+            if *embeds_license_texts && !sbom_format.is_cyclonedx() {
+                return Err("--license-text is only supported by the CycloneDX formats".into());
+            }
+            let cargo_home_option = find_cargo_home();
+            let dependencies = collect_dependencies(path, cargo_home_option.as_deref(), &licenses)?;
+            let created = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+            let sbom_document = SbomDocument::new(
+                path,
+                &dependencies,
+                &licenses,
+                &created,
+                *embeds_license_texts,
+            )?;
+            let rendered_sbom_document = sbom_document.render(*sbom_format)?;
+            if let Some(output) = output {
+                fs::write(output, rendered_sbom_document)?;
+                println!("Created {}", display_relative_path(Path::new("."), output));
+            } else {
+                print!("{}", rendered_sbom_document);
+            }
+        }
+        Commands::Policy { command } => match command {
+            PolicyCommands::Check { path } => {
+                let policy = Policy::discover(path)?;
+                let cargo_home_option = find_cargo_home();
+                let dependencies =
+                    collect_dependencies(path, cargo_home_option.as_deref(), &licenses)?;
+                let policy_report = check_policy(&dependencies, &policy);
+                print!("{}", policy_report.render());
+                if !policy_report.complies() {
+                    std::process::exit(1);
+                }
+            }
+        },
+        Commands::Compatibility { path, name } => {
+            let project_license = select_project_license(path, name.as_deref(), &licenses)?;
+            let cargo_home_option = find_cargo_home();
+            let dependencies = collect_dependencies(path, cargo_home_option.as_deref(), &licenses)?;
+            let compatibility_report = check_compatibility(&dependencies, project_license);
+            print!("{}", compatibility_report.render());
+            if !compatibility_report.is_compatible() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Headers {
+            path,
+            excludes,
+            style,
+            check,
+            use_cache,
+        } => {
+            let config = ProjectConfig::discover(path)?;
+            let files_without_header = find_files_without_header(
+                path,
+                &config.combine_exclude_globs(path, excludes)?,
+                style.or(config.header_style()).unwrap_or_default(),
+                *use_cache,
+            )?;
+            for file_without_header in &files_without_header {
+                println!("{}", display_relative_path(path, file_without_header));
+            }
+            if *check && !files_without_header.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Commands::Reuse { command } => match command {
+            ReuseCommands::Init { names } => {
+                let current_dir = Path::new(".");
+                let spdx_ids = if names.is_empty() {
+                    find_used_license_identifiers(current_dir, &[], true)?
+                        .into_iter()
+                        .collect()
+                } else {
+                    let mut spdx_ids = Vec::new();
+                    for name in names {
+                        if let Some(license) = licenses.find_license(name) {
+                            spdx_ids.push(license.spdx_id().to_string());
+                        } else {
+                            // This error scenario can also be handled using `clap`.
+                            let nonexistent_license_error =
+                                render_nonexistent_license_error(&licenses);
+                            eprintln!("{}", nonexistent_license_error);
+                            std::process::exit(2);
+                        }
+                    }
+                    spdx_ids
+                };
+                for spdx_id in spdx_ids {
+                    if let Some(license) = licenses.find_license_by_spdx_id(&spdx_id) {
+                        if let Some(license_text_path) =
+                            write_license_text(current_dir, &spdx_id, license)?
+                        {
+                            println!(
+                                "Created {}",
+                                display_relative_path(current_dir, &license_text_path)
+                            );
+                        }
+                    } else {
+                        eprintln!(
+                            "{}: no license text available for '{}'",
+                            "warning".yellow(),
+                            spdx_id.bold()
+                        );
+                    }
+                }
+            }
+            ReuseCommands::Annotate {
+                paths,
+                license,
+                copyrights,
+                precedence,
+                is_dep5,
+            } => {
+                let current_dir = Path::new(".");
+                if *is_dep5 {
+                    append_dep5_paragraph(current_dir, paths, copyrights, license)?;
+                } else {
+                    append_reuse_toml_annotation(
+                        current_dir,
+                        paths,
+                        *precedence,
+                        copyrights,
+                        license,
+                    )?;
+                }
+            }
+            ReuseCommands::Lint {
+                path,
+                excludes,
+                use_cache,
+            } => {
+                let config = ProjectConfig::discover(path)?;
+                let lint_report = lint(
+                    path,
+                    &config.combine_exclude_globs(path, excludes)?,
+                    *use_cache,
+                )?;
+                print!("{}", lint_report.render(path));
+                if !lint_report.is_compliant() {
+                    std::process::exit(1);
+                }
+            }
+        },
     }
     Ok(())
 }
@@ -233,4 +1058,61 @@ mod tests {
             expected_output
         );
     }
+
+    #[test]
+    fn find_license_by_spdx_id_with_variant_suffixes() {
+        let licenses = Licenses::from_description_file();
+        for spdx_id in ["GPL-3.0-only", "GPL-3.0-or-later", "GPL-3.0+", "gpl-3.0"] {
+            assert_eq!(
+                licenses.find_license_by_spdx_id(spdx_id).unwrap().name(),
+                "gpl-3.0"
+            );
+        }
+        assert!(licenses.find_license_by_spdx_id("GPL-3").is_none());
+    }
+
+    #[test]
+    fn detect_license_of_rendered_licenses() {
+        let licenses = Licenses::from_description_file();
+        for name in ["mit", "bsd-2-clause", "bsd-3-clause", "gpl-3.0", "agpl-3.0"] {
+            let rendered_license = licenses
+                .find_license(name)
+                .unwrap()
+                .render_licence("Jane Doe", &2024);
+            assert_eq!(
+                licenses.detect_license(&rendered_license).unwrap().name(),
+                name
+            );
+        }
+        assert!(licenses.detect_license("All rights reserved.").is_none());
+    }
+
+    #[test]
+    fn render_ids_of_license() {
+        let licenses = Licenses::from_description_file();
+        assert_eq!(
+            licenses.find_license("mit").unwrap().render_ids(),
+            "\
+SPDX            MIT
+PyPI            License :: OSI Approved :: MIT License
+Debian          Expat
+Fedora          MIT
+Maven           MIT License
+Maven URL       https://opensource.org/licenses/MIT
+"
+        );
+    }
+
+    #[test]
+    fn render_summary_of_licenses() {
+        let licenses = Licenses::from_description_file();
+        for license in licenses.licenses() {
+            let rendered_summary = license.render_summary();
+            assert!(rendered_summary.starts_with(&format!("{}\n\n", license.full_name())));
+            assert!(rendered_summary
+                .lines()
+                .skip(2)
+                .all(|line| line.chars().count() <= 80));
+        }
+    }
 }
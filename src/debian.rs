@@ -0,0 +1,363 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::annotations::Annotations;
+use crate::core::{License, Licenses};
+use crate::dep5::{self, Paragraph, DEP5_FORMAT_URL};
+use crate::reuse::{find_covered_file_scans, LICENSES_DIR_BASENAME};
+use crate::scan::scan_files;
+use crate::spdx::find_expression_identifiers;
+
+pub const DEBIAN_COPYRIGHT_FILE_PATH: &str = "debian/copyright";
+
+// The `debian/copyright` format uses lowercase operators and its own short names, with a `+`
+// suffix for "or any later version".
+fn convert_expression_to_debian(licenses: &Licenses, expression: &str) -> String {
+    expression
+        .replace('(', "( ")
+        .replace(')', " )")
+        .split_whitespace()
+        .map(|token| {
+            if ["AND", "OR", "WITH"]
+                .iter()
+                .any(|operator| operator.eq_ignore_ascii_case(token))
+            {
+                return token.to_lowercase();
+            }
+            let is_or_later = token.ends_with('+') || token.ends_with("-or-later");
+            match licenses.find_license_by_spdx_id(token) {
+                Some(license) if is_or_later => format!("{}+", license.ids().debian()),
+                Some(license) => license.ids().debian().to_string(),
+                None => token.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(" )", ")")
+}
+
+// `Copyright (c) 2024 Jane Doe` becomes `2024 Jane Doe`, as the field name says it already.
+fn strip_copyright_prefix(notice: &str) -> String {
+    let mut notice = notice.trim();
+    loop {
+        let stripped_notice = ["Copyright", "(c)", "(C)", "©"]
+            .iter()
+            .find_map(|prefix| notice.strip_prefix(prefix))
+            .map(|notice| notice.trim_start_matches([' ', ':']));
+        match stripped_notice {
+            Some(stripped_notice) => notice = stripped_notice,
+            None => return notice.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct FilesInformation {
+    copyrights: BTreeSet<String>,
+    licenses: BTreeSet<String>,
+}
+
+impl FilesInformation {
+    fn render(&self, files: &str, licenses: &Licenses) -> Paragraph {
+        let mut paragraph = Paragraph::new();
+        paragraph.push_field("Files", files);
+        let copyrights: Vec<&str> = self.copyrights.iter().map(String::as_str).collect();
+        paragraph.push_field("Copyright", &copyrights.join("\n"));
+        let debian_expressions: Vec<String> = self
+            .licenses
+            .iter()
+            .map(|expression| convert_expression_to_debian(licenses, expression))
+            .collect();
+        paragraph.push_field("License", &debian_expressions.join(" and "));
+        paragraph
+    }
+}
+
+// The standalone paragraph holding the full text of a license used in the `Files` paragraphs.
+fn render_license_paragraph(
+    root: &Path,
+    licenses: &Licenses,
+    spdx_id: &str,
+    debian_name: &str,
+) -> Paragraph {
+    let license_text_option = licenses
+        .find_license_by_spdx_id(spdx_id)
+        .map(|license| license.fetch_template().to_string())
+        .or_else(|| {
+            let license_text_path = root
+                .join(LICENSES_DIR_BASENAME)
+                .join(format!("{}.txt", spdx_id));
+            fs::read_to_string(license_text_path).ok()
+        });
+    let mut paragraph = Paragraph::new();
+    match license_text_option {
+        Some(license_text) => paragraph.push_field(
+            "License",
+            &format!("{}\n{}", debian_name, license_text.trim()),
+        ),
+        None => paragraph.push_field("License", debian_name),
+    }
+    paragraph
+}
+
+// Files without copyright or license information get the ones of the project. As the last
+// matching paragraph applies to a file, a `dir/*` paragraph is written for every directory whose
+// files all share the same information, if it differs from the one of its parent. In the other
+// directories, a paragraph is written for every file whose information differs from the parent's.
+pub fn render_debian_copyright(
+    root: &Path,
+    licenses: &Licenses,
+    project_license: &License,
+    author: &str,
+    year: u32,
+    exclude_globs: &[String],
+    use_cache: bool,
+) -> Result<String, Box<dyn Error>> {
+    let project_information = FilesInformation {
+        copyrights: BTreeSet::from([format!("{} {}", year, author)]),
+        licenses: BTreeSet::from([project_license.spdx_id().to_string()]),
+    };
+    let file_scans = scan_files(root, exclude_globs, use_cache)?;
+    let annotations = Annotations::load(root)?;
+    let mut directories_files_information: BTreeMap<PathBuf, BTreeMap<String, FilesInformation>> =
+        BTreeMap::new();
+    let mut root_files_information: BTreeMap<String, FilesInformation> = BTreeMap::new();
+    for (path, file_scan) in find_covered_file_scans(root, &file_scans, &annotations) {
+        let relative_path = path.strip_prefix(root).unwrap_or(path);
+        let file_information = FilesInformation {
+            copyrights: if file_scan.copyright_notices().is_empty() {
+                project_information.copyrights.clone()
+            } else {
+                file_scan
+                    .copyright_notices()
+                    .iter()
+                    .map(|notice| strip_copyright_prefix(notice))
+                    .collect()
+            },
+            licenses: if file_scan.license_identifiers().is_empty() {
+                project_information.licenses.clone()
+            } else {
+                file_scan.license_identifiers().iter().cloned().collect()
+            },
+        };
+        match relative_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+        {
+            Some(dir) => {
+                directories_files_information
+                    .entry(dir.to_path_buf())
+                    .or_default()
+                    .insert(
+                        relative_path.to_string_lossy().to_string(),
+                        file_information,
+                    );
+            }
+            None => {
+                if file_information != project_information {
+                    root_files_information.insert(
+                        relative_path.to_string_lossy().to_string(),
+                        file_information,
+                    );
+                }
+            }
+        }
+    }
+    let project_name = root
+        .canonicalize()?
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut header_paragraph = Paragraph::new();
+    header_paragraph.push_field("Format", DEP5_FORMAT_URL);
+    header_paragraph.push_field("Upstream-Name", &project_name);
+    let mut files_paragraphs = vec![project_information.render("*", licenses)];
+    let mut used_license_expressions = project_information.licenses.clone();
+    for (file, file_information) in &root_files_information {
+        files_paragraphs.push(file_information.render(file, licenses));
+        used_license_expressions.extend(file_information.licenses.iter().cloned());
+    }
+    let mut rendered_directories_information: BTreeMap<&PathBuf, &FilesInformation> =
+        BTreeMap::new();
+    for (dir, files_information) in &directories_files_information {
+        let parent_information = dir
+            .ancestors()
+            .skip(1)
+            .find_map(|ancestor| rendered_directories_information.get(&ancestor.to_path_buf()))
+            .copied()
+            .unwrap_or(&project_information);
+        let first_file_information = files_information.values().next().unwrap();
+        let is_homogeneous = files_information
+            .values()
+            .all(|file_information| file_information == first_file_information);
+        let differing_files_information: Vec<(String, &FilesInformation)> = if !is_homogeneous {
+            files_information
+                .iter()
+                .filter(|(_, file_information)| *file_information != parent_information)
+                .map(|(file, file_information)| (file.to_string(), file_information))
+                .collect()
+        } else if first_file_information != parent_information {
+            rendered_directories_information.insert(dir, first_file_information);
+            vec![(
+                format!("{}/*", dir.to_string_lossy()),
+                first_file_information,
+            )]
+        } else {
+            Vec::new()
+        };
+        for (files, information) in differing_files_information {
+            files_paragraphs.push(information.render(&files, licenses));
+            used_license_expressions.extend(information.licenses.iter().cloned());
+        }
+    }
+    let mut license_paragraphs = BTreeMap::new();
+    for expression in &used_license_expressions {
+        for spdx_id in find_expression_identifiers(expression) {
+            let debian_name = convert_expression_to_debian(licenses, &spdx_id);
+            license_paragraphs
+                .entry(debian_name.clone())
+                .or_insert_with(|| {
+                    render_license_paragraph(root, licenses, &spdx_id, &debian_name)
+                });
+        }
+    }
+    let mut paragraphs = vec![header_paragraph];
+    paragraphs.extend(files_paragraphs);
+    paragraphs.extend(license_paragraphs.into_values());
+    Ok(dep5::render(&paragraphs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_project_files;
+
+    #[test]
+    fn convert_expression_to_debian_short_names() {
+        let licenses = Licenses::from_description_file();
+        assert_eq!(
+            convert_expression_to_debian(&licenses, "(MIT OR Apache-2.0) AND GPL-2.0-or-later"),
+            "(Expat or Apache-2.0) and GPL-2+"
+        );
+        assert_eq!(convert_expression_to_debian(&licenses, "Zlib"), "Zlib");
+    }
+
+    #[test]
+    fn strip_copyright_prefix_of_notices() {
+        assert_eq!(
+            strip_copyright_prefix("Copyright (c) 2024 Jane Doe"),
+            "2024 Jane Doe"
+        );
+        assert_eq!(strip_copyright_prefix("© 2024 Jane Doe"), "2024 Jane Doe");
+        assert_eq!(strip_copyright_prefix("2024 Jane Doe"), "2024 Jane Doe");
+    }
+
+    #[test]
+    fn render_debian_copyright_with_directory_paragraphs() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("project");
+        create_project_files(
+            &root,
+            &[
+                ("src/main.rs", "fn main() {}\n"),
+                (
+                    "vendor/lib.c",
+                    "// SPDX-FileCopyrightText: 2020 John Doe\n// SPDX-License-Identifier: BSD-3-Clause\n",
+                ),
+                ("vendor/nested/util.c", "// Copyright (c) 2020 John Doe\n"),
+            ],
+        );
+        let debian_copyright = render_debian_copyright(
+            &root,
+            &licenses,
+            licenses.find_license("mit").unwrap(),
+            "Jane Doe",
+            2024,
+            &[],
+            false,
+        )
+        .unwrap();
+        let paragraphs = dep5::parse(&debian_copyright);
+        assert_eq!(paragraphs[0].field("Upstream-Name"), Some("project"));
+        let files_paragraphs: Vec<(&str, &str, &str)> = paragraphs
+            .iter()
+            .filter_map(|paragraph| {
+                Some((
+                    paragraph.field("Files")?,
+                    paragraph.field("Copyright")?,
+                    paragraph.field("License")?,
+                ))
+            })
+            .collect();
+        assert_eq!(
+            files_paragraphs,
+            vec![
+                ("*", "2024 Jane Doe", "Expat"),
+                ("vendor/*", "2020 John Doe", "BSD-3-clause"),
+                ("vendor/nested/*", "2020 John Doe", "Expat"),
+            ]
+        );
+        let license_names: Vec<&str> = paragraphs
+            .iter()
+            .filter(|paragraph| paragraph.field("Files").is_none())
+            .filter_map(|paragraph| paragraph.field("License"))
+            .map(|license| license.lines().next().unwrap())
+            .collect();
+        assert_eq!(license_names, vec!["BSD-3-clause", "Expat"]);
+    }
+
+    #[test]
+    fn render_debian_copyright_with_mixed_directory() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                ("src/main.rs", "fn main() {}\n"),
+                ("src/lib.rs", "pub fn lib() {}\n"),
+                (
+                    "src/vendored.c",
+                    "// SPDX-FileCopyrightText: 2020 John Doe\n// SPDX-License-Identifier: BSD-3-Clause\n",
+                ),
+                (
+                    "src/bundled.c",
+                    "// SPDX-FileCopyrightText: 2021 Jim Doe\n// SPDX-License-Identifier: Zlib\n",
+                ),
+            ],
+        );
+        let debian_copyright = render_debian_copyright(
+            temp_dir.path(),
+            &licenses,
+            licenses.find_license("mit").unwrap(),
+            "Jane Doe",
+            2024,
+            &[],
+            false,
+        )
+        .unwrap();
+        let paragraphs = dep5::parse(&debian_copyright);
+        let files_paragraphs: Vec<(&str, &str, &str)> = paragraphs
+            .iter()
+            .filter_map(|paragraph| {
+                Some((
+                    paragraph.field("Files")?,
+                    paragraph.field("Copyright")?,
+                    paragraph.field("License")?,
+                ))
+            })
+            .collect();
+        // The files of `src` have different information, so they are not merged into `src/*`.
+        assert_eq!(
+            files_paragraphs,
+            vec![
+                ("*", "2024 Jane Doe", "Expat"),
+                ("src/bundled.c", "2021 Jim Doe", "Zlib"),
+                ("src/vendored.c", "2020 John Doe", "BSD-3-clause"),
+            ]
+        );
+    }
+}
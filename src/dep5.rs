@@ -0,0 +1,123 @@
+// Machine-readable `debian/copyright` format, also used by the legacy `.reuse/dep5` file:
+// https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+pub const DEP5_FORMAT_URL: &str =
+    "https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/";
+
+// A paragraph is a list of fields. Multi-line values are stored with `\n` separated lines,
+// the first line being the one on the same line as the field name.
+#[derive(Debug, Default, PartialEq)]
+pub struct Paragraph {
+    fields: Vec<(String, String)>,
+}
+
+impl Paragraph {
+    pub fn new() -> Self {
+        Paragraph::default()
+    }
+
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field_name, _)| field_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn push_field(&mut self, name: &str, value: &str) {
+        self.fields.push((name.to_string(), value.to_string()));
+    }
+
+    fn render(&self) -> String {
+        let mut rendered_paragraph = String::new();
+        for (name, value) in &self.fields {
+            let mut value_lines = value.lines();
+            let first_line = value_lines.next().unwrap_or_default();
+            rendered_paragraph.push_str(format!("{}: {}", name, first_line).trim_end());
+            rendered_paragraph.push('\n');
+            for value_line in value_lines {
+                // Empty lines can't be continuation lines, so they are represented by a single `.`.
+                if value_line.trim().is_empty() {
+                    rendered_paragraph.push_str(" .\n");
+                } else {
+                    rendered_paragraph.push_str(&format!(" {}\n", value_line));
+                }
+            }
+        }
+        rendered_paragraph
+    }
+}
+
+pub fn parse(content: &str) -> Vec<Paragraph> {
+    let mut paragraphs = Vec::new();
+    let mut paragraph = Paragraph::new();
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            if !paragraph.fields.is_empty() {
+                paragraphs.push(paragraph);
+                paragraph = Paragraph::new();
+            }
+        } else if line.starts_with('#') {
+            continue;
+        } else if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = paragraph.fields.last_mut() {
+                let continuation_line = &line[1..];
+                value.push('\n');
+                if continuation_line.trim() != "." {
+                    value.push_str(continuation_line);
+                }
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            paragraph.push_field(name.trim(), value.trim());
+        }
+    }
+    if !paragraph.fields.is_empty() {
+        paragraphs.push(paragraph);
+    }
+    paragraphs
+}
+
+pub fn render(paragraphs: &[Paragraph]) -> String {
+    paragraphs
+        .iter()
+        .map(|paragraph| paragraph.render())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEP5_CONTENT: &str = "\
+Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/
+Upstream-Name: licensit
+
+Files: images/*
+Copyright: 2023 Jane Doe
+ 2024 John Doe
+License: MIT
+ MIT License
+ .
+ Permission is hereby granted.
+";
+
+    #[test]
+    fn parse_paragraphs_with_continuation_lines() {
+        let paragraphs = parse(DEP5_CONTENT);
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[0].field("upstream-name"), Some("licensit"));
+        assert_eq!(paragraphs[1].field("Files"), Some("images/*"));
+        assert_eq!(
+            paragraphs[1].field("Copyright"),
+            Some("2023 Jane Doe\n2024 John Doe")
+        );
+        assert_eq!(
+            paragraphs[1].field("License"),
+            Some("MIT\nMIT License\n\nPermission is hereby granted.")
+        );
+    }
+
+    #[test]
+    fn render_parsed_paragraphs() {
+        assert_eq!(render(&parse(DEP5_CONTENT)), DEP5_CONTENT);
+    }
+}
@@ -0,0 +1,781 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::consistency::find_license_files;
+use crate::core::Licenses;
+use crate::manifests::{
+    join_license_alternatives, read_manifest_license, DeclaredLicense, ManifestKind,
+    CARGO_MANIFEST_FILE_BASENAME, NPM_MANIFEST_FILE_BASENAME, PYPI_LICENSE_CLASSIFIER_PREFIX,
+};
+use crate::spdx::parse_expression;
+
+const CARGO_LOCK_FILE_BASENAME: &str = "Cargo.lock";
+const CARGO_VENDOR_DIR_BASENAME: &str = "vendor";
+const NPM_MODULES_DIR_BASENAME: &str = "node_modules";
+const PYTHON_VIRTUALENV_CONFIG_FILE_BASENAME: &str = "pyvenv.cfg";
+const PYTHON_DIST_INFO_DIR_EXTENSION: &str = "dist-info";
+const PYTHON_METADATA_FILE_BASENAME: &str = "METADATA";
+const GO_VENDOR_DIR_BASENAME: &str = "vendor";
+const GO_VENDOR_MODULES_FILE_PATH: &str = "vendor/modules.txt";
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    // Packages of the workspace and path dependencies have no source.
+    source: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CargoLock {
+    #[serde(default, rename = "package")]
+    packages: Vec<LockedPackage>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Ecosystem {
+    Cargo,
+    Npm,
+    Python,
+    Go,
+}
+
+impl Ecosystem {
+    // Type of the package URLs, see https://github.com/package-url/purl-spec.
+    fn purl_type(&self) -> &str {
+        match self {
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Npm => "npm",
+            Ecosystem::Python => "pypi",
+            Ecosystem::Go => "golang",
+        }
+    }
+}
+
+pub struct Dependency {
+    ecosystem: Ecosystem,
+    name: String,
+    version: String,
+    // `None` if the sources of the dependency aren't available locally.
+    source_dir: Option<PathBuf>,
+    declared_license: Option<DeclaredLicense>,
+    license_files: Vec<PathBuf>,
+}
+
+impl Dependency {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    // The `@` of npm scopes is percent-encoded, and PyPI names are normalized to lowercase with
+    // dashes.
+    pub fn render_purl(&self) -> String {
+        let name = match self.ecosystem {
+            Ecosystem::Npm => self.name.replace('@', "%40"),
+            Ecosystem::Python => self.name.to_lowercase().replace(['_', '.'], "-"),
+            Ecosystem::Cargo | Ecosystem::Go => self.name.to_string(),
+        };
+        format!(
+            "pkg:{}/{}@{}",
+            self.ecosystem.purl_type(),
+            name,
+            self.version
+        )
+    }
+
+    pub fn declared_license(&self) -> Option<&DeclaredLicense> {
+        self.declared_license.as_ref()
+    }
+
+    pub fn license_files(&self) -> &Vec<PathBuf> {
+        &self.license_files
+    }
+
+    // Dependencies are grouped by this label in the reports.
+    pub fn render_license(&self) -> String {
+        match (&self.source_dir, &self.declared_license) {
+            (None, _) => "Sources not found".to_string(),
+            (Some(_), Some(DeclaredLicense::Expression(expression))) => expression.to_string(),
+            (Some(_), Some(DeclaredLicense::File(file))) => format!("Custom license ({})", file),
+            (Some(_), None) => "No license declared".to_string(),
+        }
+    }
+}
+
+pub fn find_cargo_home() -> Option<PathBuf> {
+    if let Ok(cargo_home) = env::var("CARGO_HOME") {
+        return Some(PathBuf::from(cargo_home));
+    }
+    env::var("HOME")
+        .ok()
+        .map(|home| Path::new(&home).join(".cargo"))
+}
+
+// `cargo vendor` names the directories after the crate, and adds the version only if several
+// versions of the crate are vendored.
+fn find_vendored_source_dir(root: &Path, name: &str, version: &str) -> Option<PathBuf> {
+    let vendor_dir = root.join(CARGO_VENDOR_DIR_BASENAME);
+    let versioned_source_dir = vendor_dir.join(format!("{}-{}", name, version));
+    if versioned_source_dir.is_dir() {
+        return Some(versioned_source_dir);
+    }
+    let source_dir = vendor_dir.join(name);
+    let manifest = fs::read_to_string(source_dir.join(CARGO_MANIFEST_FILE_BASENAME)).ok()?;
+    let manifest = manifest.parse::<toml::Table>().ok()?;
+    let vendored_version = manifest.get("package")?.get("version")?.as_str()?;
+    (vendored_version == version).then_some(source_dir)
+}
+
+// Sources downloaded by Cargo are unpacked in a directory per registry.
+fn find_registry_source_dir(cargo_home: &Path, name: &str, version: &str) -> Option<PathBuf> {
+    fs::read_dir(cargo_home.join("registry").join("src"))
+        .ok()?
+        .filter_map(|entry_result| entry_result.ok())
+        .map(|entry| entry.path().join(format!("{}-{}", name, version)))
+        .find(|source_dir| source_dir.is_dir())
+}
+
+// The crate may be anywhere in a git repository, so its directory is found by the name and, unless
+// inherited from a workspace, the version of its package.
+fn find_git_crate_dir(dir: &Path, name: &str, version: &str) -> Option<PathBuf> {
+    let manifest_option = fs::read_to_string(dir.join(CARGO_MANIFEST_FILE_BASENAME))
+        .ok()
+        .and_then(|manifest| manifest.parse::<toml::Table>().ok());
+    if let Some(package) = manifest_option
+        .as_ref()
+        .and_then(|manifest| manifest.get("package"))
+    {
+        let is_crate = package.get("name").and_then(toml::Value::as_str) == Some(name)
+            && package
+                .get("version")
+                .and_then(toml::Value::as_str)
+                .map_or(true, |package_version| package_version == version);
+        if is_crate {
+            return Some(dir.to_path_buf());
+        }
+    }
+    let mut subdirs: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry_result| entry_result.ok())
+        .filter(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            !file_name.starts_with('.') && file_name != "target"
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    subdirs.sort();
+    subdirs
+        .iter()
+        .find_map(|subdir| find_git_crate_dir(subdir, name, version))
+}
+
+// Git dependencies, locked as `git+<url>?<reference>#<revision>`, are checked out in
+// `git/checkouts/<repository>-<hash>/<short revision>`.
+fn find_git_checkout_source_dir(
+    cargo_home: &Path,
+    source: &str,
+    name: &str,
+    version: &str,
+) -> Option<PathBuf> {
+    let (url, revision) = source.strip_prefix("git+")?.rsplit_once('#')?;
+    let url = url.split('?').next()?.trim_end_matches('/');
+    let repository_name = url.rsplit('/').next()?.trim_end_matches(".git");
+    let mut checkout_dirs: Vec<PathBuf> = fs::read_dir(cargo_home.join("git").join("checkouts"))
+        .ok()?
+        .filter_map(|entry_result| entry_result.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .rsplit_once('-')
+                .is_some_and(|(checkout_name, _)| {
+                    checkout_name.eq_ignore_ascii_case(repository_name)
+                })
+        })
+        .flat_map(|entry| fs::read_dir(entry.path()).into_iter().flatten())
+        .filter_map(|entry_result| entry_result.ok())
+        .filter(|entry| {
+            let short_revision = entry.file_name().to_string_lossy().to_string();
+            !short_revision.is_empty() && revision.starts_with(&short_revision)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    checkout_dirs.sort();
+    checkout_dirs
+        .iter()
+        .find_map(|checkout_dir| find_git_crate_dir(checkout_dir, name, version))
+}
+
+// Reads the dependencies from `Cargo.lock` and their licenses from the vendored, downloaded or
+// checked out sources.
+fn collect_cargo_dependencies(
+    root: &Path,
+    cargo_home: Option<&Path>,
+    licenses: &Licenses,
+) -> Result<Vec<Dependency>, Box<dyn Error>> {
+    let cargo_lock_path = root.join(CARGO_LOCK_FILE_BASENAME);
+    let cargo_lock_content = fs::read_to_string(&cargo_lock_path)
+        .map_err(|error| format!("{}: {}", cargo_lock_path.display(), error))?;
+    let cargo_lock: CargoLock = toml::from_str(&cargo_lock_content)?;
+    let mut dependencies = Vec::new();
+    for locked_package in cargo_lock.packages {
+        let Some(source) = locked_package.source else {
+            continue;
+        };
+        let name = locked_package.name;
+        let version = locked_package.version;
+        let source_dir = find_vendored_source_dir(root, &name, &version).or_else(|| {
+            let cargo_home = cargo_home?;
+            find_registry_source_dir(cargo_home, &name, &version)
+                .or_else(|| find_git_checkout_source_dir(cargo_home, &source, &name, &version))
+        });
+        let mut declared_license = None;
+        let mut license_files = Vec::new();
+        if let Some(source_dir) = &source_dir {
+            declared_license = read_manifest_license(
+                ManifestKind::Cargo,
+                &source_dir.join(CARGO_MANIFEST_FILE_BASENAME),
+                licenses,
+            )?;
+            license_files = find_license_files(source_dir)?;
+        }
+        dependencies.push(Dependency {
+            ecosystem: Ecosystem::Cargo,
+            name,
+            version,
+            source_dir,
+            declared_license,
+            license_files,
+        });
+    }
+    Ok(dependencies)
+}
+
+// Packages are installed in `node_modules`, in `@scope` directories for scoped packages, and in
+// nested `node_modules` directories for conflicting versions.
+fn collect_npm_dependencies(
+    modules_dir: &Path,
+    licenses: &Licenses,
+    dependencies: &mut Vec<Dependency>,
+) -> Result<(), Box<dyn Error>> {
+    let mut package_dirs = Vec::new();
+    for entry_result in fs::read_dir(modules_dir)? {
+        let entry_path = entry_result?.path();
+        let file_name = entry_path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if file_name.starts_with('.') || !entry_path.is_dir() {
+            continue;
+        }
+        if file_name.starts_with('@') {
+            for scoped_entry_result in fs::read_dir(&entry_path)? {
+                package_dirs.push(scoped_entry_result?.path());
+            }
+        } else {
+            package_dirs.push(entry_path);
+        }
+    }
+    package_dirs.sort();
+    for package_dir in package_dirs {
+        let manifest_path = package_dir.join(NPM_MANIFEST_FILE_BASENAME);
+        let Ok(manifest) = fs::read_to_string(&manifest_path) else {
+            continue;
+        };
+        let manifest = serde_json::from_str::<serde_json::Value>(&manifest)
+            .map_err(|error| format!("{}: {}", manifest_path.display(), error))?;
+        let read_field = |field: &str| {
+            manifest
+                .get(field)
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let (name, version) = (read_field("name"), read_field("version"));
+        let is_collected = dependencies
+            .iter()
+            .any(|dependency| dependency.name == name && dependency.version == version);
+        if !is_collected {
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Npm,
+                name,
+                version,
+                declared_license: read_manifest_license(
+                    ManifestKind::Npm,
+                    &manifest_path,
+                    licenses,
+                )?,
+                license_files: find_license_files(&package_dir)?,
+                source_dir: Some(package_dir.clone()),
+            });
+        }
+        let nested_modules_dir = package_dir.join(NPM_MODULES_DIR_BASENAME);
+        if nested_modules_dir.is_dir() {
+            collect_npm_dependencies(&nested_modules_dir, licenses, dependencies)?;
+        }
+    }
+    Ok(())
+}
+
+// The virtualenvs are the directories of the project holding a `pyvenv.cfg`, with their packages
+// in `lib/python3.X/site-packages` or, on Windows, in `Lib/site-packages`.
+fn find_python_site_packages_dirs(root: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut site_packages_dirs = Vec::new();
+    for entry_result in fs::read_dir(root)? {
+        let virtualenv_dir = entry_result?.path();
+        if !virtualenv_dir
+            .join(PYTHON_VIRTUALENV_CONFIG_FILE_BASENAME)
+            .is_file()
+        {
+            continue;
+        }
+        for lib_dir_basename in ["lib", "Lib"] {
+            let lib_dir = virtualenv_dir.join(lib_dir_basename);
+            site_packages_dirs.push(lib_dir.join("site-packages"));
+            if let Ok(lib_entries) = fs::read_dir(&lib_dir) {
+                for lib_entry_result in lib_entries {
+                    site_packages_dirs.push(lib_entry_result?.path().join("site-packages"));
+                }
+            }
+        }
+    }
+    site_packages_dirs.retain(|site_packages_dir| site_packages_dir.is_dir());
+    site_packages_dirs.sort();
+    site_packages_dirs.dedup();
+    Ok(site_packages_dirs)
+}
+
+// Returns the values of the fields of a core metadata file, which uses the email header format
+// and ends with the description.
+fn parse_python_metadata(metadata: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in metadata.lines() {
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = fields.last_mut() {
+                value.push('\n');
+                value.push_str(line.trim());
+            }
+        } else if let Some((field, value)) = line.split_once(':') {
+            fields.push((field.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    fields
+}
+
+// PEP 639 introduced `License-Expression`. Before that, `License` held a name or the full text of
+// the license, so the classifiers are used unless it is a valid expression.
+fn read_python_license(
+    fields: &[(String, String)],
+    licenses: &Licenses,
+) -> Option<DeclaredLicense> {
+    let find_field_values = |field: &str| -> Vec<&str> {
+        fields
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case(field))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    };
+    if let Some(expression) = find_field_values("License-Expression").first() {
+        return Some(DeclaredLicense::Expression(expression.to_string()));
+    }
+    if let Some(license) = find_field_values("License").first() {
+        if !license.contains('\n') && parse_expression(license).is_ok() {
+            return Some(DeclaredLicense::Expression(license.to_string()));
+        }
+    }
+    let alternatives = find_field_values("Classifier")
+        .into_iter()
+        .filter(|classifier| classifier.starts_with(PYPI_LICENSE_CLASSIFIER_PREFIX))
+        .filter_map(|classifier| licenses.find_license_by_pypi_classifier(classifier))
+        .map(|license| license.spdx_id().to_string())
+        .collect();
+    join_license_alternatives(alternatives)
+}
+
+// Reads the packages installed in the `*.dist-info` directories of the virtualenvs.
+fn collect_python_dependencies(
+    site_packages_dir: &Path,
+    licenses: &Licenses,
+    dependencies: &mut Vec<Dependency>,
+) -> Result<(), Box<dyn Error>> {
+    let mut dist_info_dirs = Vec::new();
+    for entry_result in fs::read_dir(site_packages_dir)? {
+        let entry_path = entry_result?.path();
+        let is_dist_info_dir = entry_path
+            .extension()
+            .is_some_and(|extension| extension == PYTHON_DIST_INFO_DIR_EXTENSION);
+        if is_dist_info_dir && entry_path.is_dir() {
+            dist_info_dirs.push(entry_path);
+        }
+    }
+    dist_info_dirs.sort();
+    for dist_info_dir in dist_info_dirs {
+        let metadata_path = dist_info_dir.join(PYTHON_METADATA_FILE_BASENAME);
+        let Ok(metadata) = fs::read_to_string(&metadata_path) else {
+            continue;
+        };
+        let fields = parse_python_metadata(&metadata);
+        let find_field_value = |field: &str| {
+            fields
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field))
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default()
+        };
+        // `License-File` paths are relative to `licenses` since PEP 639, and to the directory
+        // itself before.
+        let mut license_files: Vec<PathBuf> = fields
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("License-File"))
+            .filter_map(|(_, license_file)| {
+                [dist_info_dir.join("licenses"), dist_info_dir.clone()]
+                    .iter()
+                    .map(|license_files_dir| license_files_dir.join(license_file))
+                    .find(|license_file_path| license_file_path.is_file())
+            })
+            .collect();
+        if license_files.is_empty() {
+            license_files = find_license_files(&dist_info_dir)?;
+        }
+        dependencies.push(Dependency {
+            ecosystem: Ecosystem::Python,
+            name: find_field_value("Name"),
+            version: find_field_value("Version"),
+            declared_license: read_python_license(&fields, licenses),
+            license_files,
+            source_dir: Some(dist_info_dir),
+        });
+    }
+    Ok(())
+}
+
+// `go mod vendor` lists every module in `vendor/modules.txt` as `# <path> <version>`, possibly
+// followed by `=> <replacement>`, then its vendored packages. Modules without packages aren't
+// vendored. Go modules don't declare their license, so it is detected in their license files.
+fn collect_go_dependencies(
+    root: &Path,
+    licenses: &Licenses,
+    dependencies: &mut Vec<Dependency>,
+) -> Result<(), Box<dyn Error>> {
+    let modules = fs::read_to_string(root.join(GO_VENDOR_MODULES_FILE_PATH))?;
+    let mut vendored_modules: Vec<(String, String)> = Vec::new();
+    let mut current_module_option = None;
+    for line in modules.lines() {
+        if let Some(module) = line.strip_prefix("# ") {
+            let (module, replacement) = module.split_once(" => ").unwrap_or((module, ""));
+            let mut module_fields = module.split_whitespace();
+            let path = module_fields.next().unwrap_or_default().to_string();
+            // A replacement by another module version takes precedence over the required one.
+            let version = replacement
+                .split_whitespace()
+                .nth(1)
+                .or(module_fields.next())
+                .unwrap_or_default()
+                .to_string();
+            current_module_option = Some((path, version));
+        } else if !line.starts_with('#') && !line.trim().is_empty() {
+            if let Some(current_module) = current_module_option.take() {
+                vendored_modules.push(current_module);
+            }
+        }
+    }
+    for (path, version) in vendored_modules {
+        let source_dir = root.join(GO_VENDOR_DIR_BASENAME).join(&path);
+        if !source_dir.is_dir() {
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Go,
+                name: path,
+                version,
+                source_dir: None,
+                declared_license: None,
+                license_files: Vec::new(),
+            });
+            continue;
+        }
+        let license_files = find_license_files(&source_dir)?;
+        let mut detected_identifiers: Vec<String> = Vec::new();
+        for license_file in &license_files {
+            let license_text = String::from_utf8_lossy(&fs::read(license_file)?).to_string();
+            if let Some(license) = licenses.detect_license(&license_text) {
+                let spdx_id = license.spdx_id().to_string();
+                if !detected_identifiers.contains(&spdx_id) {
+                    detected_identifiers.push(spdx_id);
+                }
+            }
+        }
+        let declared_license = (!detected_identifiers.is_empty())
+            .then(|| DeclaredLicense::Expression(detected_identifiers.join(" AND ")));
+        dependencies.push(Dependency {
+            ecosystem: Ecosystem::Go,
+            name: path,
+            version,
+            source_dir: Some(source_dir),
+            declared_license,
+            license_files,
+        });
+    }
+    Ok(())
+}
+
+// Reads the dependencies of the Cargo, npm, Python and Go projects found in the directory, and
+// their licenses from the installed or vendored sources, without accessing the network.
+pub fn collect_dependencies(
+    root: &Path,
+    cargo_home: Option<&Path>,
+    licenses: &Licenses,
+) -> Result<Vec<Dependency>, Box<dyn Error>> {
+    let mut dependencies = Vec::new();
+    let mut is_project_found = false;
+    if root.join(CARGO_LOCK_FILE_BASENAME).is_file() {
+        dependencies.extend(collect_cargo_dependencies(root, cargo_home, licenses)?);
+        is_project_found = true;
+    }
+    let npm_modules_dir = root.join(NPM_MODULES_DIR_BASENAME);
+    if npm_modules_dir.is_dir() {
+        let mut npm_dependencies = Vec::new();
+        collect_npm_dependencies(&npm_modules_dir, licenses, &mut npm_dependencies)?;
+        dependencies.extend(npm_dependencies);
+        is_project_found = true;
+    }
+    if root.is_dir() {
+        for site_packages_dir in find_python_site_packages_dirs(root)? {
+            collect_python_dependencies(&site_packages_dir, licenses, &mut dependencies)?;
+            is_project_found = true;
+        }
+    }
+    if root.join(GO_VENDOR_MODULES_FILE_PATH).is_file() {
+        collect_go_dependencies(root, licenses, &mut dependencies)?;
+        is_project_found = true;
+    }
+    if !is_project_found {
+        return Err(format!(
+            "{}: no {}, {}, Python virtualenv or {} found",
+            root.display(),
+            CARGO_LOCK_FILE_BASENAME,
+            NPM_MODULES_DIR_BASENAME,
+            GO_VENDOR_MODULES_FILE_PATH
+        )
+        .into());
+    }
+    Ok(dependencies)
+}
+
+// One section per license, the most common licenses first.
+pub fn render_dependencies_by_license(dependencies: &[Dependency]) -> String {
+    let mut dependencies_by_license: BTreeMap<String, Vec<&Dependency>> = BTreeMap::new();
+    for dependency in dependencies {
+        dependencies_by_license
+            .entry(dependency.render_license())
+            .or_default()
+            .push(dependency);
+    }
+    let mut dependencies_by_license: Vec<(String, Vec<&Dependency>)> =
+        dependencies_by_license.into_iter().collect();
+    dependencies_by_license.sort_by_key(|(_, dependencies)| std::cmp::Reverse(dependencies.len()));
+    let mut rendered_dependencies = String::new();
+    for (license, dependencies) in dependencies_by_license {
+        rendered_dependencies.push_str(&format!("{} ({})\n", license, dependencies.len()));
+        for dependency in dependencies {
+            let license_files = dependency
+                .license_files()
+                .iter()
+                .filter_map(|license_file| license_file.file_name())
+                .map(|file_name| file_name.to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            let rendered_dependency = format!(
+                "    {: <32}{: <16}{}",
+                dependency.name(),
+                dependency.version(),
+                license_files
+            );
+            rendered_dependencies.push_str(rendered_dependency.trim_end());
+            rendered_dependencies.push('\n');
+        }
+    }
+    rendered_dependencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_project_files;
+
+    const CARGO_LOCK: &str = "\
+version = 3
+
+[[package]]
+name = \"app\"
+version = \"0.1.0\"
+dependencies = [\"either\", \"itoa\", \"missing\", \"rgb\"]
+
+[[package]]
+name = \"either\"
+version = \"1.9.0\"
+source = \"registry+https://github.com/rust-lang/crates.io-index\"
+
+[[package]]
+name = \"itoa\"
+version = \"1.0.10\"
+source = \"registry+https://github.com/rust-lang/crates.io-index\"
+
+[[package]]
+name = \"missing\"
+version = \"0.2.0\"
+source = \"registry+https://github.com/rust-lang/crates.io-index\"
+
+[[package]]
+name = \"rgb\"
+version = \"0.8.37\"
+source = \"git+https://github.com/kornelski/rust-rgb?branch=main#3bd2b3e6c1a6b1ab2a6dd3bd7bf2c0d5e8d5b3a1\"
+";
+
+    #[test]
+    fn collect_dependencies_from_vendor_dir_registry_and_git_checkouts() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("app");
+        let cargo_home = temp_dir.path().join("cargo");
+        create_project_files(
+            &root,
+            &[
+                ("Cargo.lock", CARGO_LOCK),
+                (
+                    "vendor/either/Cargo.toml",
+                    "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+                ),
+                ("vendor/either/LICENSE-MIT", "MIT License"),
+                ("vendor/either/LICENSE-APACHE", "Apache License"),
+            ],
+        );
+        create_project_files(
+            &cargo_home,
+            &[
+                (
+                    "registry/src/index.crates.io-6f17d22bba15001f/itoa-1.0.10/Cargo.toml",
+                    "[package]\nname = \"itoa\"\nversion = \"1.0.10\"\nlicense = \"MIT OR Apache-2.0\"\n",
+                ),
+                (
+                    "git/checkouts/rust-rgb-7a9e3ab5d7a5c1b2/1f0c2d4/Cargo.toml",
+                    "[package]\nname = \"rgb\"\nversion = \"0.8.36\"\nlicense = \"MIT\"\n",
+                ),
+                (
+                    "git/checkouts/rust-rgb-7a9e3ab5d7a5c1b2/3bd2b3e/Cargo.toml",
+                    "[workspace]\nmembers = [\"crates/*\"]\n",
+                ),
+                (
+                    "git/checkouts/rust-rgb-7a9e3ab5d7a5c1b2/3bd2b3e/crates/rgb/Cargo.toml",
+                    "[package]\nname = \"rgb\"\nversion = \"0.8.37\"\nlicense = \"MIT\"\n",
+                ),
+                (
+                    "git/checkouts/rust-rgb-7a9e3ab5d7a5c1b2/3bd2b3e/crates/rgb/LICENSE",
+                    "MIT License",
+                ),
+            ],
+        );
+        let dependencies = collect_dependencies(&root, Some(&cargo_home), &licenses).unwrap();
+        assert_eq!(dependencies.len(), 4);
+        assert_eq!(
+            render_dependencies_by_license(&dependencies),
+            "\
+MIT OR Apache-2.0 (2)
+    either                          1.9.0           LICENSE-APACHE, LICENSE-MIT
+    itoa                            1.0.10
+MIT (1)
+    rgb                             0.8.37          LICENSE
+Sources not found (1)
+    missing                         0.2.0
+"
+        );
+    }
+
+    #[test]
+    fn collect_npm_python_and_go_dependencies() {
+        let licenses = Licenses::from_description_file();
+        let mit_license_template = licenses.find_license("mit").unwrap().fetch_template();
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                (
+                    "node_modules/left-pad/package.json",
+                    "{\"name\": \"left-pad\", \"version\": \"1.3.0\", \"license\": \"WTFPL\"}",
+                ),
+                (
+                    "node_modules/@types/node/package.json",
+                    "{\"name\": \"@types/node\", \"version\": \"20.1.0\", \"license\": \"MIT\"}",
+                ),
+                ("node_modules/@types/node/LICENSE", "MIT License"),
+                (
+                    "node_modules/@types/node/node_modules/left-pad/package.json",
+                    "{\"name\": \"left-pad\", \"version\": \"1.1.0\", \"licenses\": [{\"type\": \"MIT\"}]}",
+                ),
+                (".venv/pyvenv.cfg", "home = /usr/bin\n"),
+                (
+                    ".venv/lib/python3.12/site-packages/requests-2.31.0.dist-info/METADATA",
+                    "Metadata-Version: 2.1\nName: requests\nVersion: 2.31.0\nLicense: Apache 2.0\nClassifier: License :: OSI Approved :: Apache Software License\nLicense-File: LICENSE\n\nDescription\n",
+                ),
+                (
+                    ".venv/lib/python3.12/site-packages/requests-2.31.0.dist-info/LICENSE",
+                    "Apache License",
+                ),
+                (
+                    ".venv/lib/python3.12/site-packages/attrs-23.2.0.dist-info/METADATA",
+                    "Metadata-Version: 2.4\nName: attrs\nVersion: 23.2.0\nLicense-Expression: MIT\nLicense-File: LICENSE\n",
+                ),
+                (
+                    ".venv/lib/python3.12/site-packages/attrs-23.2.0.dist-info/licenses/LICENSE",
+                    "MIT License",
+                ),
+                (
+                    "vendor/modules.txt",
+                    "# github.com/pkg/errors v0.9.1\n## explicit\ngithub.com/pkg/errors\n# golang.org/x/text v0.3.0\n## explicit\n",
+                ),
+                ("vendor/github.com/pkg/errors/LICENSE", mit_license_template),
+            ],
+        );
+        let dependencies = collect_dependencies(temp_dir.path(), None, &licenses).unwrap();
+        let rendered_dependencies: Vec<String> = dependencies
+            .iter()
+            .map(|dependency| {
+                format!(
+                    "{} {}: {} ({})",
+                    dependency.name(),
+                    dependency.version(),
+                    dependency.render_license(),
+                    dependency.license_files().len()
+                )
+            })
+            .collect();
+        assert_eq!(
+            rendered_dependencies,
+            vec![
+                "@types/node 20.1.0: MIT (1)",
+                "left-pad 1.1.0: MIT (0)",
+                "left-pad 1.3.0: WTFPL (0)",
+                "attrs 23.2.0: MIT (1)",
+                "requests 2.31.0: Apache-2.0 (1)",
+                "github.com/pkg/errors v0.9.1: MIT (1)",
+            ]
+        );
+    }
+
+    #[test]
+    fn collect_dependencies_without_cargo_lock() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        assert!(collect_dependencies(temp_dir.path(), None, &licenses).is_err());
+    }
+}
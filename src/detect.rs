@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+
+// Below this similarity a text isn't considered to be a copy of a license template.
+pub const MIN_LICENSE_TEXT_SIMILARITY: f64 = 0.8;
+
+// Consecutive word pairs of a text, ignoring case, punctuation and line wrapping.
+pub fn find_word_bigrams(text: &str) -> HashSet<(String, String)> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect();
+    words
+        .windows(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect()
+}
+
+// Sørensen–Dice coefficient of the word pairs, which tolerates filled in placeholders and small
+// edits of a license text.
+pub fn compute_similarity(
+    first_bigrams: &HashSet<(String, String)>,
+    second_bigrams: &HashSet<(String, String)>,
+) -> f64 {
+    let bigrams_count = first_bigrams.len() + second_bigrams.len();
+    if bigrams_count == 0 {
+        return 0.0;
+    }
+    let common_bigrams_count = first_bigrams.intersection(second_bigrams).count();
+    2.0 * common_bigrams_count as f64 / bigrams_count as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_similarity_ignores_case_punctuation_and_wrapping() {
+        let similarity = compute_similarity(
+            &find_word_bigrams("Permission is hereby granted,\nfree of charge."),
+            &find_word_bigrams("permission is hereby granted free of charge"),
+        );
+        assert_eq!(similarity, 1.0);
+    }
+
+    #[test]
+    fn compute_similarity_of_different_texts() {
+        let similarity = compute_similarity(
+            &find_word_bigrams("Permission is hereby granted, free of charge"),
+            &find_word_bigrams("Licensed under the Apache License"),
+        );
+        assert_eq!(similarity, 0.0);
+        assert_eq!(
+            compute_similarity(&find_word_bigrams(""), &find_word_bigrams("")),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use crate::annotations::Annotations;
+use crate::reuse::find_covered_file_scans;
+use crate::scan::scan_files;
+
+const SPDX_LICENSE_IDENTIFIER_TAG: &str = "SPDX-License-Identifier:";
+const SPDX_FILE_COPYRIGHT_TEXT_TAG: &str = "SPDX-FileCopyrightText:";
+
+/// Information a file must carry to have a header
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderStyle {
+    /// An SPDX license identifier
+    #[default]
+    License,
+    /// An SPDX license identifier and a copyright notice
+    LicenseAndCopyright,
+}
+
+pub fn find_license_identifiers(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.split_once(SPDX_LICENSE_IDENTIFIER_TAG))
+        .map(|(_, expression)| {
+            // Strip the closing part of block comments, e.g. `*/` or `-->`.
+            let expression = expression.trim();
+            let expression = expression
+                .strip_suffix("*/")
+                .or_else(|| expression.strip_suffix("-->"))
+                .unwrap_or(expression);
+            expression.trim().to_string()
+        })
+        .filter(|expression| !expression.is_empty())
+        .collect()
+}
+
+// Besides the SPDX tag, plain `Copyright ...` and `© ...` lines count as copyright notices.
+pub fn find_copyright_notices(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            if let Some((_, notice)) = line.split_once(SPDX_FILE_COPYRIGHT_TEXT_TAG) {
+                return Some(notice);
+            }
+            let line = line.trim_start_matches(|c: char| !c.is_alphanumeric() && c != '©');
+            if line.starts_with("Copyright") || line.starts_with('©') {
+                return Some(line);
+            }
+            None
+        })
+        .map(|notice| {
+            let notice = notice.trim();
+            let notice = notice
+                .strip_suffix("*/")
+                .or_else(|| notice.strip_suffix("-->"))
+                .unwrap_or(notice);
+            notice.trim().to_string()
+        })
+        .filter(|notice| !notice.is_empty())
+        .collect()
+}
+
+pub fn find_files_without_header(
+    root: &Path,
+    exclude_globs: &[String],
+    style: HeaderStyle,
+    use_cache: bool,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let annotations = Annotations::load(root)?;
+    let file_scans = scan_files(root, exclude_globs, use_cache)?;
+    // Like `reuse lint`, the information may come from a sidecar file or an annotation instead.
+    let files_without_header = find_covered_file_scans(root, &file_scans, &annotations)
+        .into_iter()
+        .filter(|(_, file_scan)| {
+            file_scan.license_identifiers().is_empty()
+                || (style == HeaderStyle::LicenseAndCopyright
+                    && file_scan.copyright_notices().is_empty())
+        })
+        .map(|(file, _)| file.clone())
+        .collect();
+    Ok(files_without_header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_license_identifiers_in_line_comments() {
+        let content = "// SPDX-License-Identifier: MIT OR Apache-2.0\n\nfn main() {}\n";
+        assert_eq!(
+            find_license_identifiers(content),
+            vec!["MIT OR Apache-2.0".to_string()]
+        );
+    }
+
+    #[test]
+    fn find_license_identifiers_in_block_comments() {
+        let content =
+            "/* SPDX-License-Identifier: MIT */\n<!-- SPDX-License-Identifier: CC0-1.0 -->\n";
+        assert_eq!(
+            find_license_identifiers(content),
+            vec!["MIT".to_string(), "CC0-1.0".to_string()]
+        );
+    }
+
+    #[test]
+    fn find_copyright_notices_with_spdx_tag_and_plain_notices() {
+        let content = "// SPDX-FileCopyrightText: 2024 Jane Doe\n\
+            /* Copyright (c) 2023 John Doe */\n\
+            # © 2022 ACME\n\
+            let copyright = None;\n";
+        assert_eq!(
+            find_copyright_notices(content),
+            vec![
+                "2024 Jane Doe".to_string(),
+                "Copyright (c) 2023 John Doe".to_string(),
+                "© 2022 ACME".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn find_license_identifiers_without_header() {
+        assert!(find_license_identifiers("fn main() {}\n").is_empty());
+    }
+}
@@ -1,5 +1,30 @@
+mod annotations;
+mod choose;
 mod cli;
+mod compare;
+mod compatibility;
+mod config;
+mod consistency;
 mod core;
+mod debian;
+mod dep5;
+mod deps;
+mod detect;
+mod headers;
+mod manifests;
+mod metadata;
+mod notices;
+mod output;
+mod policy;
+mod reuse;
+mod sbom;
+mod scan;
+mod search;
+mod spdx;
+mod table;
+#[cfg(test)]
+mod test_utils;
+mod walk;
 
 fn main() {
     if let Err(error) = core::run() {
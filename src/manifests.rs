@@ -0,0 +1,1328 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{value, Array, DocumentMut, InlineTable, Item, TableLike, Value};
+
+use crate::core::{License, Licenses};
+
+pub const CARGO_MANIFEST_FILE_BASENAME: &str = "Cargo.toml";
+pub const NPM_MANIFEST_FILE_BASENAME: &str = "package.json";
+pub const PYPROJECT_MANIFEST_FILE_BASENAME: &str = "pyproject.toml";
+pub const COMPOSER_MANIFEST_FILE_BASENAME: &str = "composer.json";
+pub const MAVEN_MANIFEST_FILE_BASENAME: &str = "pom.xml";
+const GEMSPEC_MANIFEST_FILE_EXTENSION: &str = "gemspec";
+pub const PYPI_LICENSE_CLASSIFIER_PREFIX: &str = "License ::";
+
+pub enum ManifestLicense<'a> {
+    Catalog(&'a License),
+    // An SPDX expression, such as a choice between licenses.
+    Expression(String),
+    // A custom license, referenced by the path of its file.
+    File(String),
+}
+
+// The license as declared in a manifest, which isn't necessarily in the catalog.
+#[derive(Debug, PartialEq)]
+pub enum DeclaredLicense {
+    Expression(String),
+    File(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ManifestKind {
+    Cargo,
+    Npm,
+    Pyproject,
+    Composer,
+    Gemspec,
+    Maven,
+}
+
+impl ManifestKind {
+    // Only some ecosystems take SPDX expressions or have a way to reference a custom license file.
+    fn supports(&self, manifest_license: &ManifestLicense) -> bool {
+        match manifest_license {
+            ManifestLicense::Catalog(_) => true,
+            ManifestLicense::Expression(_) | ManifestLicense::File(_) => matches!(
+                self,
+                ManifestKind::Cargo | ManifestKind::Npm | ManifestKind::Pyproject
+            ),
+        }
+    }
+}
+
+pub fn find_manifests(root: &Path) -> Result<Vec<(ManifestKind, PathBuf)>, Box<dyn Error>> {
+    let mut manifests = Vec::new();
+    for (manifest_kind, manifest_file_basename) in [
+        (ManifestKind::Cargo, CARGO_MANIFEST_FILE_BASENAME),
+        (ManifestKind::Npm, NPM_MANIFEST_FILE_BASENAME),
+        (ManifestKind::Pyproject, PYPROJECT_MANIFEST_FILE_BASENAME),
+        (ManifestKind::Composer, COMPOSER_MANIFEST_FILE_BASENAME),
+        (ManifestKind::Maven, MAVEN_MANIFEST_FILE_BASENAME),
+    ] {
+        let manifest_path = root.join(manifest_file_basename);
+        if manifest_path.is_file() {
+            manifests.push((manifest_kind, manifest_path));
+        }
+    }
+    let mut gemspec_paths = Vec::new();
+    for entry_result in fs::read_dir(root)? {
+        let entry_path = entry_result?.path();
+        let is_gemspec = entry_path
+            .extension()
+            .is_some_and(|extension| extension == GEMSPEC_MANIFEST_FILE_EXTENSION);
+        if is_gemspec && entry_path.is_file() {
+            gemspec_paths.push(entry_path);
+        }
+    }
+    gemspec_paths.sort();
+    manifests.extend(
+        gemspec_paths
+            .into_iter()
+            .map(|gemspec_path| (ManifestKind::Gemspec, gemspec_path)),
+    );
+    Ok(manifests)
+}
+
+// Updates all manifests found in `root`, or none of them if one of them can't represent the
+// license or fails to be updated. Returns the paths of the manifests that have changed.
+pub fn sync_manifests(
+    root: &Path,
+    manifest_license: &ManifestLicense,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let manifests = find_manifests(root)?;
+    if manifests.is_empty() {
+        return Err(format!("no package manifest found in {}", root.display()).into());
+    }
+    let unsupported_manifest_option = manifests
+        .iter()
+        .find(|(manifest_kind, _)| !manifest_kind.supports(manifest_license));
+    if let Some((_, unsupported_manifest_path)) = unsupported_manifest_option {
+        let unsupported_license = match manifest_license {
+            ManifestLicense::File(_) => "reference a custom license file",
+            _ => "declare a license expression",
+        };
+        return Err(format!(
+            "{} can't {}",
+            unsupported_manifest_path.display(),
+            unsupported_license
+        )
+        .into());
+    }
+    let mut manifest_updates = Vec::new();
+    for (manifest_kind, manifest_path) in manifests {
+        if manifest_kind == ManifestKind::Cargo {
+            manifest_updates.extend(sync_cargo_manifest(&manifest_path, manifest_license)?);
+            continue;
+        }
+        let original_manifest = fs::read_to_string(&manifest_path)?;
+        let updated_manifest = match manifest_kind {
+            ManifestKind::Npm => sync_npm_manifest(&original_manifest, manifest_license)?,
+            ManifestKind::Pyproject => {
+                sync_pyproject_manifest(&original_manifest, manifest_license)?
+            }
+            ManifestKind::Composer => sync_composer_manifest(&original_manifest, manifest_license)?,
+            ManifestKind::Gemspec => sync_gemspec_manifest(&original_manifest, manifest_license)?,
+            ManifestKind::Maven => sync_maven_manifest(&original_manifest, manifest_license)?,
+            ManifestKind::Cargo => unreachable!(),
+        };
+        if updated_manifest != original_manifest {
+            manifest_updates.push((manifest_path, updated_manifest));
+        }
+    }
+    // Nothing is written until all manifests have been updated in memory, so that a manifest
+    // failing to update leaves the other ones untouched.
+    for (manifest_path, updated_manifest) in &manifest_updates {
+        fs::write(manifest_path, updated_manifest)?;
+    }
+    Ok(manifest_updates
+        .into_iter()
+        .map(|(manifest_path, _)| manifest_path)
+        .collect())
+}
+
+// Returns `None` if the manifest doesn't declare a license.
+pub fn read_manifest_license(
+    manifest_kind: ManifestKind,
+    manifest_path: &Path,
+    licenses: &Licenses,
+) -> Result<Option<DeclaredLicense>, Box<dyn Error>> {
+    let manifest = fs::read_to_string(manifest_path)?;
+    match manifest_kind {
+        ManifestKind::Cargo => read_cargo_license(manifest_path, &manifest),
+        ManifestKind::Npm => read_npm_license(&manifest),
+        ManifestKind::Pyproject => read_pyproject_license(&manifest),
+        ManifestKind::Composer => read_composer_license(&manifest),
+        ManifestKind::Gemspec => Ok(read_gemspec_license(&manifest)),
+        ManifestKind::Maven => Ok(read_maven_license(&manifest, licenses)),
+    }
+}
+
+// Composer, RubyGems and Maven only declare licenses of the catalog, see `ManifestKind::supports`.
+fn expect_catalog_license<'a>(
+    manifest_license: &ManifestLicense<'a>,
+    manifest_file_name: &str,
+) -> Result<&'a License, Box<dyn Error>> {
+    match manifest_license {
+        ManifestLicense::Catalog(license) => Ok(license),
+        _ => Err(format!(
+            "{} can only declare a license of the list",
+            manifest_file_name
+        )
+        .into()),
+    }
+}
+
+// Several licenses listed in a manifest are a choice between them.
+pub fn join_license_alternatives(alternatives: Vec<String>) -> Option<DeclaredLicense> {
+    if alternatives.is_empty() {
+        return None;
+    }
+    Some(DeclaredLicense::Expression(alternatives.join(" OR ")))
+}
+
+// Cargo
+
+// Cargo uses `license` for SPDX expressions and `license-file` for custom licenses.
+fn cargo_license_keys(manifest_license: &ManifestLicense) -> (&'static str, &'static str) {
+    match manifest_license {
+        ManifestLicense::Catalog(_) | ManifestLicense::Expression(_) => ("license", "license-file"),
+        ManifestLicense::File(_) => ("license-file", "license"),
+    }
+}
+
+fn cargo_license_value<'a>(manifest_license: &'a ManifestLicense) -> &'a str {
+    match manifest_license {
+        ManifestLicense::Catalog(license) => license.spdx_id(),
+        ManifestLicense::Expression(expression) => expression,
+        ManifestLicense::File(file) => file,
+    }
+}
+
+fn is_inherited_from_workspace(item: Option<&Item>) -> bool {
+    item.and_then(Item::as_table_like)
+        .and_then(|table| table.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or(false)
+}
+
+// Replaces the value in place to keep the position of the key and the comments around the value.
+fn set_table_value(table: &mut dyn TableLike, key: &str, new_item: Item) {
+    if let Some(existing_value) = table.get_mut(key).and_then(Item::as_value_mut) {
+        let decor = existing_value.decor().clone();
+        if let Item::Value(mut new_value) = new_item {
+            *new_value.decor_mut() = decor;
+            *existing_value = new_value;
+            return;
+        }
+    }
+    table.insert(key, new_item);
+}
+
+fn set_cargo_license(table: &mut dyn TableLike, manifest_license: &ManifestLicense) {
+    let (license_key, conflicting_license_key) = cargo_license_keys(manifest_license);
+    table.remove(conflicting_license_key);
+    set_table_value(
+        table,
+        license_key,
+        value(cargo_license_value(manifest_license)),
+    );
+}
+
+fn set_inherited_cargo_license(table: &mut dyn TableLike, manifest_license: &ManifestLicense) {
+    let (license_key, conflicting_license_key) = cargo_license_keys(manifest_license);
+    if is_inherited_from_workspace(table.get(license_key)) {
+        return;
+    }
+    let mut inherited = InlineTable::new();
+    inherited.insert("workspace", true.into());
+    table.remove(conflicting_license_key);
+    set_table_value(table, license_key, value(inherited));
+}
+
+fn find_cargo_workspace_root_manifest(member_manifest_path: &Path) -> Option<PathBuf> {
+    let member_dir = member_manifest_path.parent()?.canonicalize().ok()?;
+    member_dir
+        .ancestors()
+        .skip(1)
+        .map(|ancestor| ancestor.join(CARGO_MANIFEST_FILE_BASENAME))
+        .find(|manifest_path| {
+            fs::read_to_string(manifest_path)
+                .ok()
+                .and_then(|manifest| manifest.parse::<DocumentMut>().ok())
+                .is_some_and(|manifest| manifest.contains_key("workspace"))
+        })
+}
+
+// Sets the license in `[workspace.package]` and `[package]`. A package that inherits its license
+// from the workspace gets it set in the manifest of the workspace root instead.
+// Returns the paths and the updated content of the manifests that have changed, without writing
+// them.
+fn sync_cargo_manifest(
+    manifest_path: &Path,
+    manifest_license: &ManifestLicense,
+) -> Result<Vec<(PathBuf, String)>, Box<dyn Error>> {
+    let original_manifest = fs::read_to_string(manifest_path)?;
+    let mut manifest = original_manifest.parse::<DocumentMut>()?;
+    let mut manifest_updates = Vec::new();
+    let workspace_package_option = manifest
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("package"))
+        .and_then(Item::as_table_like_mut);
+    let has_workspace_package = workspace_package_option.is_some();
+    if let Some(workspace_package) = workspace_package_option {
+        set_cargo_license(workspace_package, manifest_license);
+    }
+    let package_option = manifest
+        .get_mut("package")
+        .and_then(Item::as_table_like_mut);
+    let has_package = package_option.is_some();
+    if let Some(package) = package_option {
+        let is_package_license_inherited = ["license", "license-file"]
+            .iter()
+            .any(|key| is_inherited_from_workspace(package.get(key)));
+        if !is_package_license_inherited {
+            set_cargo_license(package, manifest_license);
+        } else {
+            set_inherited_cargo_license(package, manifest_license);
+            if !has_workspace_package {
+                let workspace_root_manifest_path =
+                    find_cargo_workspace_root_manifest(manifest_path).ok_or_else(|| {
+                        format!(
+                            "the license of {} is inherited, but no workspace root was found",
+                            manifest_path.display()
+                        )
+                    })?;
+                manifest_updates.extend(sync_cargo_manifest(
+                    &workspace_root_manifest_path,
+                    manifest_license,
+                )?);
+            }
+        }
+    }
+    if !has_workspace_package && !has_package {
+        return Err(format!(
+            "{} has neither a [package] nor a [workspace.package] table",
+            manifest_path.display()
+        )
+        .into());
+    }
+    let updated_manifest = manifest.to_string();
+    if updated_manifest != original_manifest {
+        manifest_updates.insert(0, (manifest_path.to_path_buf(), updated_manifest));
+    }
+    Ok(manifest_updates)
+}
+
+fn read_cargo_table_license(table: &dyn TableLike) -> Option<DeclaredLicense> {
+    if let Some(expression) = table.get("license").and_then(Item::as_str) {
+        return Some(DeclaredLicense::Expression(expression.to_string()));
+    }
+    table
+        .get("license-file")
+        .and_then(Item::as_str)
+        .map(|file| DeclaredLicense::File(file.to_string()))
+}
+
+fn read_cargo_license(
+    manifest_path: &Path,
+    manifest: &str,
+) -> Result<Option<DeclaredLicense>, Box<dyn Error>> {
+    let manifest = manifest.parse::<DocumentMut>()?;
+    let workspace_package_option = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(Item::as_table_like);
+    let Some(package) = manifest.get("package").and_then(Item::as_table_like) else {
+        return Ok(workspace_package_option.and_then(read_cargo_table_license));
+    };
+    let is_package_license_inherited = ["license", "license-file"]
+        .iter()
+        .any(|key| is_inherited_from_workspace(package.get(key)));
+    if !is_package_license_inherited {
+        return Ok(read_cargo_table_license(package));
+    }
+    if let Some(workspace_package) = workspace_package_option {
+        return Ok(read_cargo_table_license(workspace_package));
+    }
+    match find_cargo_workspace_root_manifest(manifest_path) {
+        Some(workspace_root_manifest_path) => {
+            let workspace_root_manifest = fs::read_to_string(&workspace_root_manifest_path)?;
+            read_cargo_license(&workspace_root_manifest_path, &workspace_root_manifest)
+        }
+        None => Ok(None),
+    }
+}
+
+// JSON (npm and Composer)
+
+struct JsonEntry {
+    key: String,
+    key_start: usize,
+    value_start: usize,
+    value_end: usize,
+}
+
+fn skip_json_whitespace(bytes: &[u8], mut index: usize) -> usize {
+    while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+        index += 1;
+    }
+    index
+}
+
+// `index` points at the opening quote, the returned index is right after the closing quote.
+fn skip_json_string(bytes: &[u8], mut index: usize) -> usize {
+    index += 1;
+    while index < bytes.len() && bytes[index] != b'"' {
+        if bytes[index] == b'\\' {
+            index += 1;
+        }
+        index += 1;
+    }
+    index + 1
+}
+
+fn skip_json_value(bytes: &[u8], mut index: usize) -> usize {
+    let mut depth = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => {
+                index = skip_json_string(bytes, index);
+                if depth == 0 {
+                    return index;
+                }
+                continue;
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            b',' | b'}' | b']' if depth == 0 => return index,
+            byte if depth == 0 && byte.is_ascii_whitespace() => return index,
+            _ => {}
+        }
+        index += 1;
+    }
+    index
+}
+
+// Locates the top-level entries of a JSON object without reformatting it, so that single values
+// can be replaced in place.
+fn find_json_entries(content: &str) -> Result<Vec<JsonEntry>, Box<dyn Error>> {
+    if !serde_json::from_str::<serde_json::Value>(content)?.is_object() {
+        return Err("the top-level JSON value is not an object".into());
+    }
+    let bytes = content.as_bytes();
+    let mut entries = Vec::new();
+    let mut index = skip_json_whitespace(bytes, 0) + 1;
+    loop {
+        index = skip_json_whitespace(bytes, index);
+        if bytes[index] == b'}' {
+            break;
+        }
+        let key_start = index;
+        index = skip_json_string(bytes, index);
+        let key = serde_json::from_str::<String>(&content[key_start..index])?;
+        index = skip_json_whitespace(bytes, index) + 1;
+        let value_start = skip_json_whitespace(bytes, index);
+        let value_end = skip_json_value(bytes, value_start);
+        entries.push(JsonEntry {
+            key,
+            key_start,
+            value_start,
+            value_end,
+        });
+        index = skip_json_whitespace(bytes, value_end);
+        if bytes[index] == b',' {
+            index += 1;
+        }
+    }
+    Ok(entries)
+}
+
+// Replaces the value of `key`, or inserts it after the `version` or the last entry, using the
+// indentation of that entry.
+fn set_json_value(content: &str, key: &str, json_value: &str) -> Result<String, Box<dyn Error>> {
+    let entries = find_json_entries(content)?;
+    let mut updated_content = content.to_string();
+    if let Some(entry) = entries.iter().find(|entry| entry.key == key) {
+        updated_content.replace_range(entry.value_start..entry.value_end, json_value);
+    } else if let Some(anchor_entry) = entries
+        .iter()
+        .find(|entry| entry.key == "version")
+        .or(entries.last())
+    {
+        let line_start = content[..anchor_entry.key_start]
+            .rfind('\n')
+            .map_or(0, |newline_index| newline_index + 1);
+        let indent = &content[line_start..anchor_entry.key_start];
+        let separator = if indent.trim().is_empty() {
+            format!("\n{}", indent)
+        } else {
+            " ".to_string()
+        };
+        updated_content.insert_str(
+            anchor_entry.value_end,
+            &format!(
+                ",{}{}: {}",
+                separator,
+                serde_json::to_string(key)?,
+                json_value
+            ),
+        );
+    } else {
+        let object_start = content.find('{').unwrap_or_default();
+        updated_content.insert_str(
+            object_start + 1,
+            &format!("\n  {}: {}\n", serde_json::to_string(key)?, json_value),
+        );
+    }
+    Ok(updated_content)
+}
+
+// Besides SPDX expressions, npm accepts the deprecated `{ "type": "MIT" }` objects and
+// `licenses` arrays of them.
+fn read_npm_license(manifest: &str) -> Result<Option<DeclaredLicense>, Box<dyn Error>> {
+    let manifest = serde_json::from_str::<serde_json::Value>(manifest)?;
+    let license_type = |license: &serde_json::Value| {
+        license
+            .as_str()
+            .or_else(|| license.get("type").and_then(serde_json::Value::as_str))
+            .map(|license_type| license_type.to_string())
+    };
+    if let Some(license) = manifest.get("license").and_then(license_type) {
+        if let Some(file) = license.strip_prefix("SEE LICENSE IN ") {
+            return Ok(Some(DeclaredLicense::File(file.to_string())));
+        }
+        return Ok(Some(DeclaredLicense::Expression(license)));
+    }
+    let alternatives = manifest
+        .get("licenses")
+        .and_then(serde_json::Value::as_array)
+        .map(|licenses| licenses.iter().filter_map(license_type).collect())
+        .unwrap_or_default();
+    Ok(join_license_alternatives(alternatives))
+}
+
+fn sync_npm_manifest(
+    manifest: &str,
+    manifest_license: &ManifestLicense,
+) -> Result<String, Box<dyn Error>> {
+    // npm documents `SEE LICENSE IN <filename>` for licenses without an SPDX identifier.
+    let npm_license = match manifest_license {
+        ManifestLicense::Catalog(license) => license.spdx_id().to_string(),
+        ManifestLicense::Expression(expression) => expression.to_string(),
+        ManifestLicense::File(file) => format!("SEE LICENSE IN {}", file),
+    };
+    set_json_value(manifest, "license", &serde_json::to_string(&npm_license)?)
+}
+
+fn read_composer_license(manifest: &str) -> Result<Option<DeclaredLicense>, Box<dyn Error>> {
+    let manifest = serde_json::from_str::<serde_json::Value>(manifest)?;
+    let alternatives = match manifest.get("license") {
+        Some(serde_json::Value::String(license)) => vec![license.to_string()],
+        Some(serde_json::Value::Array(licenses)) => licenses
+            .iter()
+            .filter_map(|license| license.as_str().map(|license| license.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    };
+    Ok(join_license_alternatives(alternatives))
+}
+
+fn sync_composer_manifest(
+    manifest: &str,
+    manifest_license: &ManifestLicense,
+) -> Result<String, Box<dyn Error>> {
+    let license = expect_catalog_license(manifest_license, COMPOSER_MANIFEST_FILE_BASENAME)?;
+    set_json_value(
+        manifest,
+        "license",
+        &serde_json::to_string(license.spdx_id())?,
+    )
+}
+
+// Python
+
+// Reads the PEP 639 fields, the older `license = { file = "..." }` table and Poetry's `license`.
+fn read_pyproject_license(manifest: &str) -> Result<Option<DeclaredLicense>, Box<dyn Error>> {
+    let pyproject = manifest.parse::<DocumentMut>()?;
+    if let Some(project) = pyproject.get("project").and_then(Item::as_table_like) {
+        let license_option = project.get("license");
+        if let Some(expression) = license_option.and_then(Item::as_str) {
+            return Ok(Some(DeclaredLicense::Expression(expression.to_string())));
+        }
+        let license_file_option = license_option
+            .and_then(Item::as_table_like)
+            .and_then(|license| license.get("file"))
+            .and_then(Item::as_str)
+            .or_else(|| {
+                project
+                    .get("license-files")
+                    .and_then(Item::as_array)
+                    .and_then(|license_files| license_files.get(0))
+                    .and_then(Value::as_str)
+            });
+        if let Some(license_file) = license_file_option {
+            return Ok(Some(DeclaredLicense::File(license_file.to_string())));
+        }
+    }
+    Ok(pyproject
+        .get("tool")
+        .and_then(|tool| tool.get("poetry"))
+        .and_then(|poetry| poetry.get("license"))
+        .and_then(Item::as_str)
+        .map(|expression| DeclaredLicense::Expression(expression.to_string())))
+}
+
+// Keeps the position and formatting of the first license classifier, removes the other ones.
+fn set_pypi_license_classifier(classifiers: &mut Array, license_classifier: Option<&str>) {
+    let is_license_classifier = |classifier: &Value| {
+        classifier
+            .as_str()
+            .is_some_and(|classifier| classifier.starts_with(PYPI_LICENSE_CLASSIFIER_PREFIX))
+    };
+    let first_license_classifier_index = classifiers.iter().position(is_license_classifier);
+    let mut index = 0;
+    classifiers.retain(|classifier| {
+        index += 1;
+        Some(index - 1) == first_license_classifier_index || !is_license_classifier(classifier)
+    });
+    match (first_license_classifier_index, license_classifier) {
+        (Some(first_license_classifier_index), Some(license_classifier)) => {
+            let existing_classifier = classifiers.get_mut(first_license_classifier_index).unwrap();
+            let decor = existing_classifier.decor().clone();
+            *existing_classifier = license_classifier.into();
+            *existing_classifier.decor_mut() = decor;
+        }
+        (Some(first_license_classifier_index), None) => {
+            classifiers.remove(first_license_classifier_index);
+        }
+        (None, Some(license_classifier)) => {
+            let decor_option = classifiers.iter().last().map(|last| last.decor().clone());
+            classifiers.push(license_classifier);
+            if let Some(decor) = decor_option {
+                *classifiers.iter_mut().last().unwrap().decor_mut() = decor;
+            }
+        }
+        (None, None) => {}
+    }
+}
+
+// Follows PEP 639: `license` holds an SPDX expression and `license-files` custom license files.
+// License classifiers are only updated if the project lists classifiers already.
+fn sync_pyproject_manifest(
+    manifest: &str,
+    manifest_license: &ManifestLicense,
+) -> Result<String, Box<dyn Error>> {
+    let mut pyproject = manifest.parse::<DocumentMut>()?;
+    let mut has_license_table = false;
+    if let Some(project) = pyproject
+        .get_mut("project")
+        .and_then(Item::as_table_like_mut)
+    {
+        has_license_table = true;
+        let license_classifier = match manifest_license {
+            ManifestLicense::Catalog(license) => {
+                set_table_value(project, "license", value(license.spdx_id()));
+                Some(license.ids().pypi())
+            }
+            // There are no classifiers for license expressions.
+            ManifestLicense::Expression(expression) => {
+                set_table_value(project, "license", value(expression.as_str()));
+                None
+            }
+            ManifestLicense::File(file) => {
+                project.remove("license");
+                let mut license_files = Array::new();
+                license_files.push(file.as_str());
+                set_table_value(project, "license-files", value(license_files));
+                None
+            }
+        };
+        if let Some(classifiers) = project.get_mut("classifiers").and_then(Item::as_array_mut) {
+            set_pypi_license_classifier(classifiers, license_classifier);
+        }
+    }
+    if let Some(poetry) = pyproject
+        .get_mut("tool")
+        .and_then(|tool| tool.get_mut("poetry"))
+        .and_then(Item::as_table_like_mut)
+    {
+        has_license_table = true;
+        match manifest_license {
+            ManifestLicense::Catalog(license) => {
+                set_table_value(poetry, "license", value(license.spdx_id()));
+            }
+            ManifestLicense::Expression(expression) => {
+                set_table_value(poetry, "license", value(expression.as_str()));
+            }
+            ManifestLicense::File(_) => {}
+        }
+    }
+    if !has_license_table {
+        return Err(format!(
+            "{} has neither a [project] nor a [tool.poetry] table",
+            PYPROJECT_MANIFEST_FILE_BASENAME
+        )
+        .into());
+    }
+    Ok(pyproject.to_string())
+}
+
+// Ruby
+
+// Splits `  spec.license = "MIT"` into the indentation, the receiver and the attribute name.
+fn parse_gemspec_assignment(line: &str) -> Option<(&str, &str, &str)> {
+    let assignment = line.trim_start();
+    let indent = &line[..line.len() - assignment.len()];
+    let (receiver, rest) = assignment.split_once('.')?;
+    if receiver.is_empty() || !receiver.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let attribute_end = rest
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(rest.len());
+    let (attribute, rest) = rest.split_at(attribute_end);
+    let rest = rest.trim_start();
+    if !rest.starts_with('=') || rest.starts_with("==") {
+        return None;
+    }
+    Some((indent, receiver, attribute))
+}
+
+// Collects the quoted strings of the `license` or `licenses` assignment, which may span several
+// lines.
+fn read_gemspec_license(manifest: &str) -> Option<DeclaredLicense> {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let license_line_index = lines.iter().position(|line| {
+        parse_gemspec_assignment(line)
+            .is_some_and(|(_, _, attribute)| attribute == "license" || attribute == "licenses")
+    })?;
+    let mut assignment = String::new();
+    for line in &lines[license_line_index..] {
+        assignment.push_str(line);
+        if !lines[license_line_index].contains('[') || line.contains(']') {
+            break;
+        }
+    }
+    let (_, value) = assignment.split_once('=')?;
+    let alternatives = value
+        .split(['"', '\''])
+        .skip(1)
+        .step_by(2)
+        .map(|license| license.to_string())
+        .collect();
+    join_license_alternatives(alternatives)
+}
+
+fn sync_gemspec_manifest(
+    manifest: &str,
+    manifest_license: &ManifestLicense,
+) -> Result<String, Box<dyn Error>> {
+    let license = expect_catalog_license(
+        manifest_license,
+        &format!("*.{}", GEMSPEC_MANIFEST_FILE_EXTENSION),
+    )?;
+    let render_license_line = |indent: &str, receiver: &str| {
+        format!("{}{}.license = {:?}", indent, receiver, license.spdx_id())
+    };
+    let mut lines: Vec<String> = manifest.lines().map(|line| line.to_string()).collect();
+    let license_line_index_option = lines.iter().position(|line| {
+        parse_gemspec_assignment(line)
+            .is_some_and(|(_, _, attribute)| attribute == "license" || attribute == "licenses")
+    });
+    if let Some(license_line_index) = license_line_index_option {
+        let (indent, receiver, _) = parse_gemspec_assignment(&lines[license_line_index]).unwrap();
+        let license_line = render_license_line(indent, receiver);
+        // A `licenses` array may span several lines.
+        let mut license_line_end_index = license_line_index;
+        if lines[license_line_index].contains('[') {
+            while !lines[license_line_end_index].contains(']')
+                && license_line_end_index + 1 < lines.len()
+            {
+                license_line_end_index += 1;
+            }
+        }
+        lines.splice(license_line_index..=license_line_end_index, [license_line]);
+    } else {
+        let version_line_index = lines
+            .iter()
+            .position(|line| {
+                parse_gemspec_assignment(line)
+                    .is_some_and(|(_, _, attribute)| attribute == "version")
+            })
+            .ok_or("the gemspec has neither a license nor a version attribute")?;
+        let (indent, receiver, _) = parse_gemspec_assignment(&lines[version_line_index]).unwrap();
+        let license_line = render_license_line(indent, receiver);
+        lines.insert(version_line_index + 1, license_line);
+    }
+    let mut updated_manifest = lines.join("\n");
+    if manifest.ends_with('\n') {
+        updated_manifest.push('\n');
+    }
+    Ok(updated_manifest)
+}
+
+// Maven
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+// An element of `pom.xml`, from the start of its start tag to the end of its end tag.
+struct XmlElement<'a> {
+    name: &'a str,
+    start: usize,
+    end: usize,
+}
+
+// `tag` starts with `<`, the returned index is right after the `>` closing the tag, ignoring the
+// ones in attribute values.
+fn find_xml_tag_end(tag: &str) -> Option<usize> {
+    let mut quote_option = None;
+    for (index, character) in tag.char_indices() {
+        match (quote_option, character) {
+            (None, '"' | '\'') => quote_option = Some(character),
+            (Some(quote), _) if character == quote => quote_option = None,
+            (None, '>') => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+// Returns the direct children of the top-level `<project>` element and the start of its end tag.
+// Comments, CDATA sections, processing instructions and the doctype are skipped, so that elements
+// commented out or nested in other ones, such as `<profiles>`, are never mistaken for them.
+fn find_maven_project_children(
+    manifest: &str,
+) -> Result<(Vec<XmlElement<'_>>, usize), Box<dyn Error>> {
+    let malformed_error = || format!("{} is not well-formed", MAVEN_MANIFEST_FILE_BASENAME);
+    let mut project_children = Vec::new();
+    let mut depth = 0;
+    let mut child_option = None;
+    let mut index = 0;
+    while let Some(tag_offset) = manifest[index..].find('<') {
+        let tag_start = index + tag_offset;
+        let tag = &manifest[tag_start..];
+        let skipped_delimiters_option = [
+            ("<!--", "-->"),
+            ("<![CDATA[", "]]>"),
+            ("<?", "?>"),
+            ("<!", ">"),
+        ]
+        .into_iter()
+        .find(|(opening_delimiter, _)| tag.starts_with(opening_delimiter));
+        if let Some((_, closing_delimiter)) = skipped_delimiters_option {
+            index = tag_start
+                + tag.find(closing_delimiter).ok_or_else(malformed_error)?
+                + closing_delimiter.len();
+            continue;
+        }
+        let tag_end = tag_start + find_xml_tag_end(tag).ok_or_else(malformed_error)?;
+        let name = tag[1..]
+            .trim_start_matches('/')
+            .split(|character: char| {
+                character.is_whitespace() || character == '/' || character == '>'
+            })
+            .next()
+            .unwrap_or_default();
+        if tag.starts_with("</") {
+            depth -= 1;
+            match depth {
+                0 => return Ok((project_children, tag_start)),
+                1 => project_children.extend(child_option.take().map(|(name, start)| XmlElement {
+                    name,
+                    start,
+                    end: tag_end,
+                })),
+                _ => {}
+            }
+        } else {
+            if depth == 0 && name != "project" {
+                break;
+            }
+            let is_empty_element = manifest[..tag_end - 1].ends_with('/');
+            if depth == 1 && is_empty_element {
+                project_children.push(XmlElement {
+                    name,
+                    start: tag_start,
+                    end: tag_end,
+                });
+            } else if depth == 1 {
+                child_option = Some((name, tag_start));
+            }
+            if !is_empty_element {
+                depth += 1;
+            }
+        }
+        index = tag_end;
+    }
+    Err(format!("{} has no <project> element", MAVEN_MANIFEST_FILE_BASENAME).into())
+}
+
+// Maven licenses are free-form names, which are mapped to SPDX identifiers through the catalog.
+fn read_maven_license(manifest: &str, licenses: &Licenses) -> Option<DeclaredLicense> {
+    let (project_children, _) = find_maven_project_children(manifest).ok()?;
+    let licenses_element = project_children
+        .iter()
+        .find(|child| child.name == "licenses")?;
+    let alternatives = manifest[licenses_element.start..licenses_element.end]
+        .split("<name>")
+        .skip(1)
+        .filter_map(|name_element| name_element.split_once("</name>"))
+        .map(|(name, _)| {
+            let name = unescape_xml(name.trim());
+            licenses
+                .find_license_by_maven_name(&name)
+                .map_or(name, |license| license.spdx_id().to_string())
+        })
+        .collect();
+    join_license_alternatives(alternatives)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn find_line_indent(content: &str, index: usize) -> &str {
+    let line_start = content[..index]
+        .rfind('\n')
+        .map_or(0, |newline_index| newline_index + 1);
+    let line = &content[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+// Replaces the `<licenses>` element, or inserts it at the end of `<project>`, following the
+// indentation of `<modelVersion>`.
+fn sync_maven_manifest(
+    manifest: &str,
+    manifest_license: &ManifestLicense,
+) -> Result<String, Box<dyn Error>> {
+    let license = expect_catalog_license(manifest_license, MAVEN_MANIFEST_FILE_BASENAME)?;
+    let (project_children, project_end) = find_maven_project_children(manifest)?;
+    let find_project_child = |name: &str| project_children.iter().find(|child| child.name == name);
+    let indent_unit = find_project_child("modelVersion")
+        .map(|model_version| find_line_indent(manifest, model_version.start))
+        .filter(|indent| !indent.is_empty())
+        .unwrap_or("    ");
+    let render_licenses = |indent: &str| {
+        let maven_license = license.ids().maven();
+        format!(
+            "<licenses>\n\
+            {indent}{unit}<license>\n\
+            {indent}{unit}{unit}<name>{}</name>\n\
+            {indent}{unit}{unit}<url>{}</url>\n\
+            {indent}{unit}</license>\n\
+            {indent}</licenses>",
+            escape_xml(maven_license.name()),
+            escape_xml(maven_license.url()),
+            indent = indent,
+            unit = indent_unit,
+        )
+    };
+    let mut updated_manifest = manifest.to_string();
+    if let Some(licenses_element) = find_project_child("licenses") {
+        let indent = find_line_indent(manifest, licenses_element.start);
+        updated_manifest.replace_range(
+            licenses_element.start..licenses_element.end,
+            &render_licenses(indent),
+        );
+    } else {
+        let line_start = manifest[..project_end]
+            .rfind('\n')
+            .map_or(0, |newline_index| newline_index + 1);
+        updated_manifest.insert_str(
+            line_start,
+            &format!("{}{}\n", indent_unit, render_licenses(indent_unit)),
+        );
+    }
+    Ok(updated_manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Licenses;
+
+    fn sync_cargo_manifest_content(
+        manifest_content: &str,
+        manifest_license: &ManifestLicense,
+    ) -> String {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manifest_path = temp_dir.path().join(CARGO_MANIFEST_FILE_BASENAME);
+        fs::write(&manifest_path, manifest_content).unwrap();
+        sync_manifests(temp_dir.path(), manifest_license).unwrap();
+        fs::read_to_string(manifest_path).unwrap()
+    }
+
+    #[test]
+    fn sync_cargo_manifest_keeps_formatting_and_comments() {
+        let licenses = Licenses::from_description_file();
+        let manifest_content = "\
+[package]
+name = \"crate\"
+license = \"MIT\"   # Chosen by the legal team
+version = \"0.1.0\"
+
+# Dependencies
+[dependencies]
+";
+        assert_eq!(
+            sync_cargo_manifest_content(
+                manifest_content,
+                &ManifestLicense::Catalog(licenses.find_license("apache-2.0").unwrap())
+            ),
+            manifest_content.replace("\"MIT\"", "\"Apache-2.0\"")
+        );
+    }
+
+    #[test]
+    fn sync_cargo_manifest_with_license_file() {
+        let manifest_content = "\
+[package]
+name = \"crate\"
+license = \"MIT\"
+";
+        assert_eq!(
+            sync_cargo_manifest_content(
+                manifest_content,
+                &ManifestLicense::File("LICENSE".to_string())
+            ),
+            "\
+[package]
+name = \"crate\"
+license-file = \"LICENSE\"
+"
+        );
+    }
+
+    #[test]
+    fn sync_cargo_manifest_with_workspace_package() {
+        let licenses = Licenses::from_description_file();
+        let manifest_content = "\
+[workspace]
+members = [\"crates/*\"]
+
+[workspace.package]
+version = \"0.1.0\"
+
+[package]
+name = \"crate\"
+license.workspace = true
+";
+        assert_eq!(
+            sync_cargo_manifest_content(
+                manifest_content,
+                &ManifestLicense::Catalog(licenses.find_license("mit").unwrap())
+            ),
+            manifest_content.replace(
+                "version = \"0.1.0\"\n",
+                "version = \"0.1.0\"\nlicense = \"MIT\"\n"
+            )
+        );
+    }
+
+    #[test]
+    fn sync_cargo_manifest_of_workspace_member() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root_manifest_path = temp_dir.path().join(CARGO_MANIFEST_FILE_BASENAME);
+        fs::write(
+            &root_manifest_path,
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        fs::create_dir(temp_dir.path().join("member")).unwrap();
+        let member_manifest_path = temp_dir
+            .path()
+            .join("member")
+            .join(CARGO_MANIFEST_FILE_BASENAME);
+        fs::write(
+            &member_manifest_path,
+            "[package]\nname = \"member\"\nlicense = { workspace = true }\n",
+        )
+        .unwrap();
+        let updated_manifest_paths = sync_manifests(
+            &temp_dir.path().join("member"),
+            &ManifestLicense::Expression("MIT OR Apache-2.0".to_string()),
+        )
+        .unwrap();
+        assert_eq!(updated_manifest_paths.len(), 1);
+        assert_eq!(
+            fs::read_to_string(member_manifest_path).unwrap(),
+            "[package]\nname = \"member\"\nlicense = { workspace = true }\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root_manifest_path).unwrap(),
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nlicense = \"MIT OR Apache-2.0\"\n"
+        );
+    }
+
+    #[test]
+    fn sync_npm_manifest_replaces_and_inserts_license() {
+        let licenses = Licenses::from_description_file();
+        let mit_license = ManifestLicense::Catalog(licenses.find_license("mit").unwrap());
+        assert_eq!(
+            sync_npm_manifest(
+                "{\n  \"name\": \"package\",\n  \"license\": { \"type\": \"ISC\" },\n  \"main\": \"index.js\"\n}\n",
+                &mit_license
+            )
+            .unwrap(),
+            "{\n  \"name\": \"package\",\n  \"license\": \"MIT\",\n  \"main\": \"index.js\"\n}\n"
+        );
+        assert_eq!(
+            sync_npm_manifest(
+                "{\n\t\"name\": \"package\",\n\t\"version\": \"1.0.0\",\n\t\"scripts\": {}\n}\n",
+                &ManifestLicense::File("LICENSE.txt".to_string())
+            )
+            .unwrap(),
+            "{\n\t\"name\": \"package\",\n\t\"version\": \"1.0.0\",\n\t\"license\": \"SEE LICENSE IN LICENSE.txt\",\n\t\"scripts\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn sync_composer_manifest_with_license_array() {
+        let licenses = Licenses::from_description_file();
+        assert_eq!(
+            sync_composer_manifest(
+                "{\"name\": \"vendor/package\", \"license\": [\"MIT\", \"GPL-3.0-or-later\"]}",
+                &ManifestLicense::Catalog(licenses.find_license("apache-2.0").unwrap())
+            )
+            .unwrap(),
+            "{\"name\": \"vendor/package\", \"license\": \"Apache-2.0\"}"
+        );
+    }
+
+    #[test]
+    fn sync_pyproject_manifest_with_classifiers() {
+        let licenses = Licenses::from_description_file();
+        assert_eq!(
+            sync_pyproject_manifest(
+                "\
+[project]
+name = \"package\"
+license = { text = \"MIT\" }
+classifiers = [
+    \"License :: OSI Approved :: MIT License\",
+    \"Programming Language :: Python :: 3\",
+    \"License :: Other/Proprietary License\",
+]
+",
+                &ManifestLicense::Catalog(licenses.find_license("apache-2.0").unwrap())
+            )
+            .unwrap(),
+            "\
+[project]
+name = \"package\"
+license = \"Apache-2.0\"
+classifiers = [
+    \"License :: OSI Approved :: Apache Software License\",
+    \"Programming Language :: Python :: 3\",
+]
+"
+        );
+    }
+
+    #[test]
+    fn sync_gemspec_manifest_replaces_and_inserts_license() {
+        let licenses = Licenses::from_description_file();
+        let mit_license = ManifestLicense::Catalog(licenses.find_license("mit").unwrap());
+        assert_eq!(
+            sync_gemspec_manifest(
+                "Gem::Specification.new do |s|\n  s.name = \"gem\"\n  s.licenses = [\n    \"Apache-2.0\",\n  ]\nend\n",
+                &mit_license
+            )
+            .unwrap(),
+            "Gem::Specification.new do |s|\n  s.name = \"gem\"\n  s.license = \"MIT\"\nend\n"
+        );
+        assert_eq!(
+            sync_gemspec_manifest(
+                "Gem::Specification.new do |spec|\n  spec.name = \"gem\"\n  spec.version = Gem::VERSION\nend\n",
+                &mit_license
+            )
+            .unwrap(),
+            "Gem::Specification.new do |spec|\n  spec.name = \"gem\"\n  spec.version = Gem::VERSION\n  spec.license = \"MIT\"\nend\n"
+        );
+    }
+
+    #[test]
+    fn sync_maven_manifest_replaces_and_inserts_licenses() {
+        let licenses = Licenses::from_description_file();
+        let mit_license = ManifestLicense::Catalog(licenses.find_license("mit").unwrap());
+        let mit_licenses_element = concat!(
+            "  <licenses>\n",
+            "    <license>\n",
+            "      <name>MIT License</name>\n",
+            "      <url>https://opensource.org/licenses/MIT</url>\n",
+            "    </license>\n",
+            "  </licenses>\n",
+        );
+        assert_eq!(
+            sync_maven_manifest(
+                "<project>\n  <modelVersion>4.0.0</modelVersion>\n</project>\n",
+                &mit_license
+            )
+            .unwrap(),
+            format!(
+                "<project>\n  <modelVersion>4.0.0</modelVersion>\n{}</project>\n",
+                mit_licenses_element
+            )
+        );
+        assert_eq!(
+            sync_maven_manifest(
+                "<project>\n  <modelVersion>4.0.0</modelVersion>\n  <licenses>\n    <license><name>Apache-2.0</name></license>\n  </licenses>\n  <dependencies/>\n</project>\n",
+                &mit_license
+            )
+            .unwrap(),
+            format!(
+                "<project>\n  <modelVersion>4.0.0</modelVersion>\n{}  <dependencies/>\n</project>\n",
+                mit_licenses_element
+            )
+        );
+    }
+
+    #[test]
+    fn sync_maven_manifest_ignores_nested_and_commented_licenses() {
+        let licenses = Licenses::from_description_file();
+        let manifest = "\
+<?xml version=\"1.0\"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <!-- <licenses></licenses> -->
+  <profiles>
+    <profile>
+      <licenses><license><name>GPL-3.0</name></license></licenses>
+    </profile>
+  </profiles>
+</project>
+";
+        let updated_manifest = sync_maven_manifest(
+            manifest,
+            &ManifestLicense::Catalog(licenses.find_license("mit").unwrap()),
+        )
+        .unwrap();
+        assert!(updated_manifest.starts_with(&manifest[..manifest.len() - "</project>\n".len()]));
+        assert!(updated_manifest.ends_with("  </licenses>\n</project>\n"));
+        assert_eq!(
+            read_maven_license(&updated_manifest, &licenses),
+            Some(DeclaredLicense::Expression("MIT".to_string()))
+        );
+        assert_eq!(read_maven_license(manifest, &licenses), None);
+        assert!(sync_composer_manifest(
+            "{}",
+            &ManifestLicense::Expression("MIT OR Apache-2.0".to_string())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sync_manifests_with_unsupported_license_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let npm_manifest = "{\"name\": \"package\"}";
+        fs::write(
+            temp_dir.path().join(NPM_MANIFEST_FILE_BASENAME),
+            npm_manifest,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("package.gemspec"), "").unwrap();
+        assert!(sync_manifests(
+            temp_dir.path(),
+            &ManifestLicense::File("LICENSE".to_string())
+        )
+        .is_err());
+        // Nothing is written if one of the manifests can't represent the license.
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(NPM_MANIFEST_FILE_BASENAME)).unwrap(),
+            npm_manifest
+        );
+    }
+
+    #[test]
+    fn sync_manifests_with_license_expression() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join(NPM_MANIFEST_FILE_BASENAME),
+            "{\"name\": \"package\"}",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join(PYPROJECT_MANIFEST_FILE_BASENAME),
+            "[project]\nname = \"package\"\nclassifiers = [\"License :: OSI Approved :: MIT License\"]\n",
+        )
+        .unwrap();
+        let expression = ManifestLicense::Expression("MIT OR Apache-2.0".to_string());
+        sync_manifests(temp_dir.path(), &expression).unwrap();
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(NPM_MANIFEST_FILE_BASENAME)).unwrap(),
+            "{\"name\": \"package\", \"license\": \"MIT OR Apache-2.0\"}"
+        );
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(PYPROJECT_MANIFEST_FILE_BASENAME)).unwrap(),
+            "[project]\nname = \"package\"\nclassifiers = []\nlicense = \"MIT OR Apache-2.0\"\n"
+        );
+        // Composer, RubyGems and Maven have no license expressions.
+        fs::write(temp_dir.path().join("package.gemspec"), "").unwrap();
+        assert!(sync_manifests(temp_dir.path(), &expression).is_err());
+    }
+
+    #[test]
+    fn sync_manifests_with_failing_manifest() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let npm_manifest = "{\"name\": \"package\"}";
+        fs::write(
+            temp_dir.path().join(NPM_MANIFEST_FILE_BASENAME),
+            npm_manifest,
+        )
+        .unwrap();
+        // The npm manifest is updated first, then `pom.xml` fails as it has no `<project>`.
+        fs::write(temp_dir.path().join(MAVEN_MANIFEST_FILE_BASENAME), "<pom/>").unwrap();
+        assert!(sync_manifests(
+            temp_dir.path(),
+            &ManifestLicense::Catalog(licenses.find_license("mit").unwrap())
+        )
+        .is_err());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join(NPM_MANIFEST_FILE_BASENAME)).unwrap(),
+            npm_manifest
+        );
+    }
+
+    #[test]
+    fn read_manifest_licenses_of_several_ecosystems() {
+        let licenses = Licenses::from_description_file();
+        let expression =
+            |expression: &str| Some(DeclaredLicense::Expression(expression.to_string()));
+        assert_eq!(
+            read_npm_license("{\"licenses\": [{\"type\": \"MIT\"}, {\"type\": \"ISC\"}]}").unwrap(),
+            expression("MIT OR ISC")
+        );
+        assert_eq!(
+            read_npm_license("{\"license\": \"SEE LICENSE IN LICENSE.txt\"}").unwrap(),
+            Some(DeclaredLicense::File("LICENSE.txt".to_string()))
+        );
+        assert_eq!(
+            read_pyproject_license("[project]\nlicense = { file = \"LICENSE\" }\n").unwrap(),
+            Some(DeclaredLicense::File("LICENSE".to_string()))
+        );
+        assert_eq!(
+            read_gemspec_license("Gem::Specification.new do |s|\n  s.licenses = [\n    'MIT',\n    \"Apache-2.0\"\n  ]\nend\n"),
+            expression("MIT OR Apache-2.0")
+        );
+        assert_eq!(
+            read_maven_license(
+                "<project><licenses><license><name>MIT License</name></license><license><name>Custom</name></license></licenses></project>",
+                &licenses
+            ),
+            expression("MIT OR Custom")
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+// The rules of https://choosealicense.com/appendix/, with the same tags in `licenses.yml`.
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Permission {
+    CommercialUse,
+    Modifications,
+    Distribution,
+    PatentUse,
+    PrivateUse,
+}
+
+impl Permission {
+    pub fn label(&self) -> &str {
+        match self {
+            Permission::CommercialUse => "Commercial use",
+            Permission::Modifications => "Modification",
+            Permission::Distribution => "Distribution",
+            Permission::PatentUse => "Patent use",
+            Permission::PrivateUse => "Private use",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Condition {
+    IncludeCopyright,
+    #[serde(rename = "include-copyright--source")]
+    IncludeCopyrightSource,
+    DocumentChanges,
+    DiscloseSource,
+    NetworkUseDisclose,
+    SameLicense,
+    #[serde(rename = "same-license--file")]
+    SameLicenseFile,
+    #[serde(rename = "same-license--library")]
+    SameLicenseLibrary,
+}
+
+impl Condition {
+    pub fn label(&self) -> &str {
+        match self {
+            Condition::IncludeCopyright => "License and copyright notice",
+            Condition::IncludeCopyrightSource => "License and copyright notice for source",
+            Condition::DocumentChanges => "State changes",
+            Condition::DiscloseSource => "Disclose source",
+            Condition::NetworkUseDisclose => "Network use is distribution",
+            Condition::SameLicense => "Same license",
+            Condition::SameLicenseFile => "Same license (file)",
+            Condition::SameLicenseLibrary => "Same license (library)",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Limitation {
+    TrademarkUse,
+    Liability,
+    PatentUse,
+    Warranty,
+}
+
+impl Limitation {
+    pub fn label(&self) -> &str {
+        match self {
+            Limitation::TrademarkUse => "Trademark use",
+            Limitation::Liability => "Liability",
+            Limitation::PatentUse => "Patent use",
+            Limitation::Warranty => "Warranty",
+        }
+    }
+}
+
+/// Scope of the obligation to share changes under the same license
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Copyleft {
+    /// The whole work, including code combined with the licensed code
+    Strong,
+    /// The licensed files or library only
+    Weak,
+    /// No obligation, as for permissive licenses
+    None,
+}
+
+impl Copyleft {
+    pub fn from_conditions(conditions: &[Condition]) -> Self {
+        if conditions.contains(&Condition::SameLicense) {
+            Copyleft::Strong
+        } else if conditions.iter().any(|condition| {
+            matches!(
+                condition,
+                Condition::SameLicenseFile | Condition::SameLicenseLibrary
+            )
+        }) {
+            Copyleft::Weak
+        } else {
+            Copyleft::None
+        }
+    }
+}
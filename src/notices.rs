@@ -0,0 +1,278 @@
+use clap::ValueEnum;
+use std::error::Error;
+use std::fs;
+
+use crate::core::Licenses;
+use crate::deps::Dependency;
+use crate::manifests::DeclaredLicense;
+use crate::spdx::find_expression_identifiers;
+
+const NOTICES_TITLE: &str = "Third-Party Notices";
+const NOTICES_INTRODUCTION: &str =
+    "This software includes the following third-party dependencies, distributed under the licenses below.";
+
+/// Format of the notices file
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum NoticesFormat {
+    /// Plain text
+    #[default]
+    Text,
+    /// Markdown
+    Markdown,
+    /// HTML
+    Html,
+}
+
+impl NoticesFormat {
+    pub fn default_file_name(&self) -> &str {
+        match self {
+            NoticesFormat::Text => "THIRD-PARTY-NOTICES",
+            NoticesFormat::Markdown => "THIRD-PARTY-NOTICES.md",
+            NoticesFormat::Html => "THIRD-PARTY-NOTICES.html",
+        }
+    }
+}
+
+// A license text with the dependencies that ship it, or that declare it without shipping a text.
+struct LicenseText {
+    title: String,
+    text: String,
+    dependencies: Vec<String>,
+}
+
+// Returns the license texts, each one only once, and the dependencies without any license text.
+fn collect_license_texts(
+    dependencies: &[Dependency],
+    licenses: &Licenses,
+) -> Result<(Vec<LicenseText>, Vec<String>), Box<dyn Error>> {
+    let mut license_texts: Vec<LicenseText> = Vec::new();
+    let mut dependencies_without_text = Vec::new();
+    for dependency in dependencies {
+        let rendered_dependency = format!("{} {}", dependency.name(), dependency.version());
+        let mut dependency_license_texts = Vec::new();
+        for license_file in dependency.license_files() {
+            let text = String::from_utf8_lossy(&fs::read(license_file)?).to_string();
+            let title = licenses
+                .detect_license(&text)
+                .map(|license| license.spdx_id().to_string())
+                .unwrap_or_else(|| {
+                    license_file
+                        .file_name()
+                        .map(|file_name| file_name.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                });
+            dependency_license_texts.push((title, text));
+        }
+        // Crates that ship no license text get the embedded template of their declared licenses.
+        if let (true, Some(DeclaredLicense::Expression(expression))) = (
+            dependency_license_texts.is_empty(),
+            dependency.declared_license(),
+        ) {
+            for identifier in find_expression_identifiers(expression) {
+                if let Some(license) = licenses.find_license_by_spdx_id(&identifier) {
+                    dependency_license_texts.push((
+                        license.spdx_id().to_string(),
+                        license.fetch_template().to_string(),
+                    ));
+                }
+            }
+        }
+        if dependency_license_texts.is_empty() {
+            dependencies_without_text.push(rendered_dependency);
+            continue;
+        }
+        for (title, text) in dependency_license_texts {
+            let text = text.trim().to_string();
+            match license_texts
+                .iter_mut()
+                .find(|license_text| license_text.text == text)
+            {
+                Some(license_text) => license_text.dependencies.push(rendered_dependency.clone()),
+                None => license_texts.push(LicenseText {
+                    title,
+                    text,
+                    dependencies: vec![rendered_dependency.clone()],
+                }),
+            }
+        }
+    }
+    license_texts.sort_by(|first, second| first.title.cmp(&second.title));
+    Ok((license_texts, dependencies_without_text))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_text_notices(dependencies: &[Dependency], license_texts: &[LicenseText]) -> String {
+    let separator = "=".repeat(80);
+    let mut rendered_notices = format!("{}\n\n{}\n\n", NOTICES_TITLE, NOTICES_INTRODUCTION);
+    for dependency in dependencies {
+        rendered_notices.push_str(&format!(
+            "    {} {}: {}\n",
+            dependency.name(),
+            dependency.version(),
+            dependency.render_license()
+        ));
+    }
+    for license_text in license_texts {
+        rendered_notices.push_str(&format!(
+            "\n{}\n{}\nUsed by: {}\n{}\n\n{}\n",
+            separator,
+            license_text.title,
+            license_text.dependencies.join(", "),
+            separator,
+            license_text.text
+        ));
+    }
+    rendered_notices
+}
+
+fn render_markdown_notices(dependencies: &[Dependency], license_texts: &[LicenseText]) -> String {
+    let mut rendered_notices = format!("# {}\n\n{}\n\n", NOTICES_TITLE, NOTICES_INTRODUCTION);
+    for dependency in dependencies {
+        rendered_notices.push_str(&format!(
+            "- {} {}: {}\n",
+            dependency.name(),
+            dependency.version(),
+            dependency.render_license()
+        ));
+    }
+    for license_text in license_texts {
+        rendered_notices.push_str(&format!(
+            "\n## {}\n\nUsed by: {}\n\n```\n{}\n```\n",
+            license_text.title,
+            license_text.dependencies.join(", "),
+            license_text.text
+        ));
+    }
+    rendered_notices
+}
+
+fn render_html_notices(dependencies: &[Dependency], license_texts: &[LicenseText]) -> String {
+    let mut rendered_notices = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n<p>{}</p>\n<ul>\n",
+        NOTICES_INTRODUCTION,
+        title = NOTICES_TITLE
+    );
+    for dependency in dependencies {
+        rendered_notices.push_str(&format!(
+            "<li>{} {}: {}</li>\n",
+            escape_html(dependency.name()),
+            escape_html(dependency.version()),
+            escape_html(&dependency.render_license())
+        ));
+    }
+    rendered_notices.push_str("</ul>\n");
+    for license_text in license_texts {
+        rendered_notices.push_str(&format!(
+            "<h2>{}</h2>\n<p>Used by: {}</p>\n<pre>{}</pre>\n",
+            escape_html(&license_text.title),
+            escape_html(&license_text.dependencies.join(", ")),
+            escape_html(&license_text.text)
+        ));
+    }
+    rendered_notices.push_str("</body>\n</html>\n");
+    rendered_notices
+}
+
+// Returns the notices and the dependencies for which no license text was found.
+pub fn render_notices(
+    dependencies: &[Dependency],
+    licenses: &Licenses,
+    format: NoticesFormat,
+) -> Result<(String, Vec<String>), Box<dyn Error>> {
+    let (license_texts, dependencies_without_text) = collect_license_texts(dependencies, licenses)?;
+    let rendered_notices = match format {
+        NoticesFormat::Text => render_text_notices(dependencies, &license_texts),
+        NoticesFormat::Markdown => render_markdown_notices(dependencies, &license_texts),
+        NoticesFormat::Html => render_html_notices(dependencies, &license_texts),
+    };
+    Ok((rendered_notices, dependencies_without_text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deps::collect_dependencies;
+    use crate::test_utils::create_project_files;
+    use std::path::Path;
+
+    fn collect_test_dependencies(root: &Path, licenses: &Licenses) -> Vec<Dependency> {
+        let mut cargo_lock = "version = 3\n".to_string();
+        for name in ["first", "second", "third", "fourth"] {
+            cargo_lock.push_str(&format!(
+                "\n[[package]]\nname = \"{}\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+                name
+            ));
+        }
+        create_project_files(
+            root,
+            &[
+                ("Cargo.lock", &cargo_lock),
+                (
+                    "vendor/first/Cargo.toml",
+                    "[package]\nname = \"first\"\nversion = \"1.0.0\"\nlicense = \"MIT\"\n",
+                ),
+                ("vendor/first/LICENSE", "Custom <license> text\n"),
+                (
+                    "vendor/second/Cargo.toml",
+                    "[package]\nname = \"second\"\nversion = \"1.0.0\"\nlicense = \"MIT\"\n",
+                ),
+                ("vendor/second/LICENSE.txt", "Custom <license> text"),
+                (
+                    "vendor/third/Cargo.toml",
+                    "[package]\nname = \"third\"\nversion = \"1.0.0\"\nlicense = \"Unlicense\"\n",
+                ),
+            ],
+        );
+        collect_dependencies(root, None, licenses).unwrap()
+    }
+
+    #[test]
+    fn render_notices_deduplicates_license_texts() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dependencies = collect_test_dependencies(temp_dir.path(), &licenses);
+        let (license_texts, dependencies_without_text) =
+            collect_license_texts(&dependencies, &licenses).unwrap();
+        assert_eq!(dependencies_without_text, vec!["fourth 1.0.0".to_string()]);
+        assert_eq!(license_texts.len(), 2);
+        assert_eq!(license_texts[0].title, "LICENSE");
+        assert_eq!(
+            license_texts[0].dependencies,
+            vec!["first 1.0.0".to_string(), "second 1.0.0".to_string()]
+        );
+        // The third dependency ships no text, so the embedded template is used.
+        assert_eq!(license_texts[1].title, "Unlicense");
+        assert_eq!(
+            license_texts[1].text,
+            licenses
+                .find_license("unlicense")
+                .unwrap()
+                .fetch_template()
+                .trim()
+        );
+    }
+
+    #[test]
+    fn render_markdown_and_html_notices() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dependencies = collect_test_dependencies(temp_dir.path(), &licenses);
+        let (markdown_notices, _) =
+            render_notices(&dependencies, &licenses, NoticesFormat::Markdown).unwrap();
+        assert!(markdown_notices.starts_with("# Third-Party Notices\n"));
+        assert!(markdown_notices.contains("- first 1.0.0: MIT\n"));
+        assert!(markdown_notices.contains(
+            "## LICENSE\n\nUsed by: first 1.0.0, second 1.0.0\n\n```\nCustom <license> text\n```\n"
+        ));
+        let (html_notices, _) =
+            render_notices(&dependencies, &licenses, NoticesFormat::Html).unwrap();
+        assert!(html_notices.contains("<pre>Custom &lt;license&gt; text</pre>"));
+        assert!(html_notices.contains("<li>fourth 1.0.0: Sources not found</li>"));
+    }
+}
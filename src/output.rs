@@ -0,0 +1,177 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::error::Error;
+
+use crate::core::{License, LicenseIds};
+use crate::metadata::{Condition, Copyleft, Limitation, Permission};
+
+/// Format of the output of `list`, `show` and `info`
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// JSON
+    Json,
+    /// YAML
+    Yaml,
+}
+
+// A license of the catalog, as printed by `list` and `info`.
+#[derive(Serialize)]
+pub struct LicenseDescription<'a> {
+    name: &'a str,
+    spdx_id: &'a str,
+    full_name: &'a str,
+    aliases: &'a [String],
+    deprecated: bool,
+    osi_approved: bool,
+    fsf_libre: bool,
+    copyleft: Copyleft,
+    permissions: &'a [Permission],
+    conditions: &'a [Condition],
+    limitations: &'a [Limitation],
+    summary: &'a str,
+    ids: &'a LicenseIds,
+}
+
+impl<'a> LicenseDescription<'a> {
+    pub fn new(license: &'a License) -> Self {
+        LicenseDescription {
+            name: license.name(),
+            spdx_id: license.spdx_id(),
+            full_name: license.full_name(),
+            aliases: license.aliases(),
+            deprecated: license.deprecated(),
+            osi_approved: license.osi_approved(),
+            fsf_libre: license.fsf_libre(),
+            copyleft: license.copyleft(),
+            permissions: license.permissions(),
+            conditions: license.conditions(),
+            limitations: license.limitations(),
+            summary: license.summary(),
+            ids: license.ids(),
+        }
+    }
+}
+
+// A placeholder of the template, and the value it was replaced with, `None` for templates.
+#[derive(Serialize)]
+pub struct PlaceholderValue<'a> {
+    field: &'a str,
+    placeholder: &'a str,
+    value: Option<String>,
+}
+
+impl<'a> PlaceholderValue<'a> {
+    pub fn new(field: &'a str, placeholder: &'a str, value: Option<String>) -> Self {
+        PlaceholderValue {
+            field,
+            placeholder,
+            value,
+        }
+    }
+}
+
+// The text of a license, as printed by `show`.
+#[derive(Serialize)]
+pub struct RenderedLicense<'a> {
+    name: &'a str,
+    spdx_id: &'a str,
+    text: &'a str,
+    placeholders: Vec<PlaceholderValue<'a>>,
+}
+
+impl<'a> RenderedLicense<'a> {
+    pub fn new(
+        license: &'a License,
+        text: &'a str,
+        placeholders: Vec<PlaceholderValue<'a>>,
+    ) -> Self {
+        RenderedLicense {
+            name: license.name(),
+            spdx_id: license.spdx_id(),
+            text,
+            placeholders,
+        }
+    }
+}
+
+// The identifiers of a license, as printed by `show --ids`.
+#[derive(Serialize)]
+pub struct LicenseIdentifiers<'a> {
+    name: &'a str,
+    spdx_id: &'a str,
+    ids: &'a LicenseIds,
+}
+
+impl<'a> LicenseIdentifiers<'a> {
+    pub fn new(license: &'a License) -> Self {
+        LicenseIdentifiers {
+            name: license.name(),
+            spdx_id: license.spdx_id(),
+            ids: license.ids(),
+        }
+    }
+}
+
+// The summary of a license, as printed by `show --summary`.
+#[derive(Serialize)]
+pub struct LicenseSummary<'a> {
+    name: &'a str,
+    spdx_id: &'a str,
+    summary: &'a str,
+}
+
+impl<'a> LicenseSummary<'a> {
+    pub fn new(license: &'a License) -> Self {
+        LicenseSummary {
+            name: license.name(),
+            spdx_id: license.spdx_id(),
+            summary: license.summary(),
+        }
+    }
+}
+
+// Only for the structured formats, the text format is rendered by each command.
+pub fn render_structured_output<T: Serialize>(
+    value: &T,
+    format: OutputFormat,
+) -> Result<String, Box<dyn Error>> {
+    match format {
+        OutputFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(value)?)),
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(value)?),
+        OutputFormat::Text => Err("the text format is rendered by each command".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Licenses;
+
+    #[test]
+    fn render_structured_output_with_license_identifiers() {
+        let licenses = Licenses::from_description_file();
+        let license_identifiers = LicenseIdentifiers::new(licenses.find_license("mit").unwrap());
+        assert_eq!(
+            render_structured_output(&license_identifiers, OutputFormat::Yaml).unwrap(),
+            "\
+name: mit
+spdx_id: MIT
+ids:
+  pypi: 'License :: OSI Approved :: MIT License'
+  debian: Expat
+  fedora: MIT
+  maven:
+    name: MIT License
+    url: https://opensource.org/licenses/MIT
+"
+        );
+        let rendered_json =
+            render_structured_output(&license_identifiers, OutputFormat::Json).unwrap();
+        assert!(rendered_json.starts_with("{\n  \"name\": \"mit\",\n"));
+        assert!(rendered_json.ends_with("}\n"));
+        assert!(render_structured_output(&license_identifiers, OutputFormat::Text).is_err());
+    }
+}
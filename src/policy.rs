@@ -0,0 +1,334 @@
+use serde::Deserialize;
+use std::error::Error;
+use std::path::Path;
+
+use crate::config::{ProjectConfig, PROJECT_CONFIG_FILE_BASENAME};
+use crate::deps::Dependency;
+use crate::manifests::DeclaredLicense;
+use crate::spdx::{
+    canonicalize_license_identifier, parse_expression, strip_license_variant, Expression,
+};
+
+// Verdicts from the best to the worst, so that `OR` takes the minimum and `AND` the maximum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Verdict {
+    Allowed,
+    Review,
+    Unlisted,
+    Denied,
+}
+
+impl Verdict {
+    fn render(&self) -> &str {
+        match self {
+            Verdict::Allowed => "Allowed",
+            Verdict::Review => "Needs review",
+            Verdict::Unlisted => "Not covered by the policy",
+            Verdict::Denied => "Denied",
+        }
+    }
+
+    fn violates_policy(&self) -> bool {
+        *self >= Verdict::Unlisted
+    }
+}
+
+// License identifiers, or `<license> WITH <exception>` expressions, compared case-insensitively.
+// Variants of a license, such as `GPL-3.0`, `GPL-3.0-only` and `GPL-3.0-or-later`, are covered by
+// any of them, unless one of the lists names the variant itself.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    review: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+impl Policy {
+    // Reads the `[policy]` table of the `.licensit.toml` found in the directory or its ancestors.
+    pub fn discover(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        ProjectConfig::discover(dir)?
+            .policy()
+            .cloned()
+            .ok_or_else(|| {
+                format!("no [policy] section in {}", PROJECT_CONFIG_FILE_BASENAME).into()
+            })
+    }
+
+    // A license listed in several lists gets the strictest verdict.
+    fn classify_with(&self, identifier: &str, normalize: fn(&str) -> String) -> Option<Verdict> {
+        // The license of a `<license> WITH <exception>` expression is normalized, the exception
+        // is only lowercased.
+        let normalize_expression =
+            |expression: &str| match expression.split_whitespace().collect::<Vec<&str>>()[..] {
+                [license, operator, exception] if operator.eq_ignore_ascii_case("WITH") => {
+                    format!("{} with {}", normalize(license), exception.to_lowercase())
+                }
+                _ => normalize(expression),
+            };
+        let normalized_identifier = normalize_expression(identifier);
+        let is_listed = |identifiers: &Vec<String>| {
+            identifiers.iter().any(|listed_identifier| {
+                normalize_expression(listed_identifier) == normalized_identifier
+            })
+        };
+        if is_listed(&self.deny) {
+            Some(Verdict::Denied)
+        } else if is_listed(&self.review) {
+            Some(Verdict::Review)
+        } else if is_listed(&self.allow) {
+            Some(Verdict::Allowed)
+        } else {
+            None
+        }
+    }
+
+    fn classify(&self, identifier: &str) -> Option<Verdict> {
+        self.classify_with(identifier, canonicalize_license_identifier)
+            .or_else(|| self.classify_with(identifier, strip_license_variant))
+    }
+
+    // Returns the verdict of the expression and the licenses it results from. Any alternative of
+    // `OR` may be chosen, so the best one wins, while all operands of `AND` apply.
+    fn evaluate(&self, expression: &Expression) -> (Verdict, Vec<String>) {
+        match expression {
+            Expression::License(identifier) => (
+                self.classify(identifier).unwrap_or(Verdict::Unlisted),
+                vec![identifier.to_string()],
+            ),
+            Expression::With(license, exception) => {
+                let rendered_expression = match license.as_ref() {
+                    Expression::License(identifier) => format!("{} WITH {}", identifier, exception),
+                    _ => return self.evaluate(license),
+                };
+                match self.classify(&rendered_expression) {
+                    Some(verdict) => (verdict, vec![rendered_expression]),
+                    None => self.evaluate(license),
+                }
+            }
+            Expression::Or(operands) => operands
+                .iter()
+                .map(|operand| self.evaluate(operand))
+                .min_by_key(|(verdict, _)| *verdict)
+                .unwrap(),
+            Expression::And(operands) => {
+                let evaluated_operands: Vec<(Verdict, Vec<String>)> = operands
+                    .iter()
+                    .map(|operand| self.evaluate(operand))
+                    .collect();
+                let verdict = evaluated_operands
+                    .iter()
+                    .map(|(verdict, _)| *verdict)
+                    .max()
+                    .unwrap();
+                let mut reasons: Vec<String> = Vec::new();
+                for (_, operand_reasons) in evaluated_operands
+                    .into_iter()
+                    .filter(|(operand_verdict, _)| *operand_verdict == verdict)
+                {
+                    for reason in operand_reasons {
+                        if !reasons.contains(&reason) {
+                            reasons.push(reason);
+                        }
+                    }
+                }
+                (verdict, reasons)
+            }
+        }
+    }
+
+    // Dependencies without a valid SPDX expression can't be judged and aren't covered.
+    fn evaluate_dependency(&self, dependency: &Dependency) -> (Verdict, Vec<String>) {
+        if let Some(DeclaredLicense::Expression(expression)) = dependency.declared_license() {
+            if let Ok(parsed_expression) = parse_expression(expression) {
+                return self.evaluate(&parsed_expression);
+            }
+        }
+        (Verdict::Unlisted, Vec::new())
+    }
+}
+
+struct DependencyCheck {
+    name: String,
+    version: String,
+    license: String,
+    verdict: Verdict,
+    reasons: Vec<String>,
+}
+
+pub struct PolicyReport {
+    dependencies: Vec<DependencyCheck>,
+}
+
+impl PolicyReport {
+    pub fn complies(&self) -> bool {
+        !self
+            .dependencies
+            .iter()
+            .any(|dependency| dependency.verdict.violates_policy())
+    }
+
+    // One section per verdict other than allowed, the worst first.
+    pub fn render(&self) -> String {
+        let mut rendered_report = String::new();
+        for verdict in [Verdict::Denied, Verdict::Unlisted, Verdict::Review] {
+            let dependencies: Vec<&DependencyCheck> = self
+                .dependencies
+                .iter()
+                .filter(|dependency| dependency.verdict == verdict)
+                .collect();
+            if dependencies.is_empty() {
+                continue;
+            }
+            rendered_report.push_str(&format!("{} ({})\n", verdict.render(), dependencies.len()));
+            for dependency in dependencies {
+                let mut rendered_license = dependency.license.to_string();
+                // Point out the licenses that matter when the expression combines several.
+                if dependency.reasons.len() > 1
+                    || dependency
+                        .reasons
+                        .first()
+                        .is_some_and(|reason| *reason != dependency.license)
+                {
+                    rendered_license
+                        .push_str(&format!(" (because of {})", dependency.reasons.join(", ")));
+                }
+                rendered_report.push_str(&format!(
+                    "    {: <32}{: <16}{}\n",
+                    dependency.name, dependency.version, rendered_license
+                ));
+            }
+        }
+        let violations_count = self
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.verdict.violates_policy())
+            .count();
+        if violations_count == 0 {
+            rendered_report.push_str(&format!(
+                "All {} dependencies comply with the policy\n",
+                self.dependencies.len()
+            ));
+        } else {
+            rendered_report.push_str(&format!(
+                "{} of {} dependencies violate the policy\n",
+                violations_count,
+                self.dependencies.len()
+            ));
+        }
+        rendered_report
+    }
+}
+
+pub fn check_policy(dependencies: &[Dependency], policy: &Policy) -> PolicyReport {
+    let dependencies = dependencies
+        .iter()
+        .map(|dependency| {
+            let (verdict, reasons) = policy.evaluate_dependency(dependency);
+            DependencyCheck {
+                name: dependency.name().to_string(),
+                version: dependency.version().to_string(),
+                license: dependency.render_license(),
+                verdict,
+                reasons,
+            }
+        })
+        .collect();
+    PolicyReport { dependencies }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn create_policy() -> Policy {
+        toml::from_str::<Policy>(
+            "allow = [\"MIT\", \"Apache-2.0\", \"GPL-2.0-only WITH Classpath-exception-2.0\"]\nreview = [\"MPL-2.0\"]\ndeny = [\"AGPL-3.0-only\", \"GPL-2.0-only\"]\n",
+        )
+        .unwrap()
+    }
+
+    fn evaluate(policy: &Policy, expression: &str) -> (Verdict, Vec<String>) {
+        policy.evaluate(&parse_expression(expression).unwrap())
+    }
+
+    #[test]
+    fn evaluate_expressions_against_policy() {
+        let policy = create_policy();
+        let reasons = |identifiers: &[&str]| {
+            identifiers
+                .iter()
+                .map(|identifier| identifier.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(
+            evaluate(&policy, "mit"),
+            (Verdict::Allowed, reasons(&["mit"]))
+        );
+        assert_eq!(
+            evaluate(&policy, "AGPL-3.0-only OR MIT"),
+            (Verdict::Allowed, reasons(&["MIT"]))
+        );
+        assert_eq!(
+            evaluate(&policy, "MIT AND (MPL-2.0 OR AGPL-3.0-only)"),
+            (Verdict::Review, reasons(&["MPL-2.0"]))
+        );
+        assert_eq!(
+            evaluate(&policy, "MIT AND AGPL-3.0-only AND Unlicense"),
+            (Verdict::Denied, reasons(&["AGPL-3.0-only"]))
+        );
+        assert_eq!(
+            evaluate(&policy, "MIT AND Unlicense"),
+            (Verdict::Unlisted, reasons(&["Unlicense"]))
+        );
+        assert_eq!(
+            evaluate(&policy, "GPL-2.0-only WITH Classpath-exception-2.0"),
+            (
+                Verdict::Allowed,
+                reasons(&["GPL-2.0-only WITH Classpath-exception-2.0"])
+            )
+        );
+        assert_eq!(
+            evaluate(&policy, "GPL-2.0-only WITH LLVM-exception"),
+            (Verdict::Denied, reasons(&["GPL-2.0-only"]))
+        );
+    }
+
+    #[test]
+    fn evaluate_variants_of_listed_licenses() {
+        let policy = toml::from_str::<Policy>(
+            "allow = [\"GPL-3.0-or-later\", \"LGPL-2.1-or-later\"]\ndeny = [\"LGPL-2.1-only\"]\n",
+        )
+        .unwrap();
+        for expression in ["GPL-3.0", "GPL-3.0+", "gpl-3.0-only", "LGPL-2.1+"] {
+            assert_eq!(evaluate(&policy, expression).0, Verdict::Allowed);
+        }
+        // The variant listed itself wins over the other variants.
+        assert_eq!(evaluate(&policy, "LGPL-2.1").0, Verdict::Denied);
+        assert_eq!(evaluate(&policy, "GPL-2.0+").0, Verdict::Unlisted);
+    }
+
+    #[test]
+    fn discover_policy_in_project_config() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let nested_dir = temp_dir.path().join("server");
+        fs::create_dir_all(&nested_dir).unwrap();
+        assert!(Policy::discover(&nested_dir).is_err());
+        let config_path = temp_dir.path().join(PROJECT_CONFIG_FILE_BASENAME);
+        fs::write(&config_path, "license = \"mit\"\n").unwrap();
+        assert!(Policy::discover(&nested_dir).is_err());
+        fs::write(
+            &config_path,
+            "license = \"mit\"\n\n[policy]\nallow = [\"MIT\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Policy::discover(&nested_dir).unwrap().classify("MIT"),
+            Some(Verdict::Allowed)
+        );
+    }
+}
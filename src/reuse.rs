@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::annotations::{Annotations, REUSE_TOML_FILE_BASENAME};
+use crate::core::License;
+use crate::scan::{scan_files, FileScan};
+use crate::spdx::find_expression_identifiers;
+use crate::walk::display_relative_path;
+
+pub const LICENSES_DIR_BASENAME: &str = "LICENSES";
+const LICENSE_SIDECAR_FILE_EXTENSION: &str = "license";
+// Files which are themselves license texts don't have to carry copyright and license information.
+pub const LICENSE_FILE_BASENAME_PREFIXES: [&str; 3] = ["LICENSE", "LICENCE", "COPYING"];
+
+pub fn is_exempt_file(root: &Path, path: &Path) -> bool {
+    if path.starts_with(root.join(LICENSES_DIR_BASENAME))
+        || path == root.join(REUSE_TOML_FILE_BASENAME)
+    {
+        return true;
+    }
+    if path
+        .extension()
+        .is_some_and(|extension| extension == LICENSE_SIDECAR_FILE_EXTENSION)
+    {
+        return true;
+    }
+    let file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy())
+        .unwrap_or_default();
+    LICENSE_FILE_BASENAME_PREFIXES
+        .iter()
+        .any(|prefix| file_name.starts_with(prefix))
+}
+
+// `image.png.license` carries the information for `image.png`, which can't have a header itself.
+fn find_license_sidecar_file(path: &Path) -> PathBuf {
+    let mut sidecar_file = path.as_os_str().to_owned();
+    sidecar_file.push(".");
+    sidecar_file.push(LICENSE_SIDECAR_FILE_EXTENSION);
+    PathBuf::from(sidecar_file)
+}
+
+// License texts are identified by their file stem, e.g. `LICENSES/MIT.txt` provides `MIT`.
+fn find_provided_license_identifiers(root: &Path) -> Result<BTreeSet<String>, Box<dyn Error>> {
+    let licenses_dir = root.join(LICENSES_DIR_BASENAME);
+    let mut provided_license_identifiers = BTreeSet::new();
+    if !licenses_dir.is_dir() {
+        return Ok(provided_license_identifiers);
+    }
+    for entry_result in fs::read_dir(licenses_dir)? {
+        let entry_path = entry_result?.path();
+        if let Some(file_stem) = entry_path.file_stem() {
+            provided_license_identifiers.insert(file_stem.to_string_lossy().to_string());
+        }
+    }
+    Ok(provided_license_identifiers)
+}
+
+pub fn find_used_license_identifiers(
+    root: &Path,
+    exclude_globs: &[String],
+    use_cache: bool,
+) -> Result<BTreeSet<String>, Box<dyn Error>> {
+    let file_scans = scan_files(root, exclude_globs, use_cache)?;
+    let annotations = Annotations::load(root)?;
+    Ok(find_covered_file_scans(root, &file_scans, &annotations)
+        .values()
+        .flat_map(|file_scan| file_scan.license_identifiers())
+        .flat_map(|expression| find_expression_identifiers(expression))
+        .collect())
+}
+
+// Maps every file that has to carry copyright and license information to the information found
+// in its header or sidecar file, combined with the matching annotation.
+pub fn find_covered_file_scans<'a>(
+    root: &Path,
+    file_scans: &'a BTreeMap<PathBuf, FileScan>,
+    annotations: &Annotations,
+) -> BTreeMap<&'a PathBuf, FileScan> {
+    file_scans
+        .iter()
+        .filter(|(path, _)| !is_exempt_file(root, path))
+        .map(|(path, file_scan)| {
+            let sidecar_file_scan = file_scans.get(&find_license_sidecar_file(path));
+            let file_scan = sidecar_file_scan.unwrap_or(file_scan);
+            (path, annotations.apply(root, path, file_scan))
+        })
+        .collect()
+}
+
+// Returns the path of the created license text, or `None` if a text for the license exists already.
+pub fn write_license_text(
+    root: &Path,
+    spdx_id: &str,
+    license: &License,
+) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    if find_provided_license_identifiers(root)?.contains(spdx_id) {
+        return Ok(None);
+    }
+    let licenses_dir = root.join(LICENSES_DIR_BASENAME);
+    fs::create_dir_all(&licenses_dir)?;
+    let license_text_path = licenses_dir.join(format!("{}.txt", spdx_id));
+    fs::write(&license_text_path, license.fetch_template())?;
+    Ok(Some(license_text_path))
+}
+
+pub struct LintReport {
+    missing_licenses: BTreeSet<String>,
+    unused_licenses: BTreeSet<String>,
+    files_without_copyright: Vec<PathBuf>,
+    files_without_license: Vec<PathBuf>,
+}
+
+impl LintReport {
+    pub fn is_compliant(&self) -> bool {
+        self.missing_licenses.is_empty()
+            && self.unused_licenses.is_empty()
+            && self.files_without_copyright.is_empty()
+            && self.files_without_license.is_empty()
+    }
+
+    pub fn render(&self, root: &Path) -> String {
+        if self.is_compliant() {
+            return "The project is compliant with the REUSE specification\n".to_string();
+        }
+        let mut rendered_report = String::new();
+        let render_identifiers = |identifiers: &BTreeSet<String>| {
+            identifiers
+                .iter()
+                .cloned()
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        if !self.missing_licenses.is_empty() {
+            rendered_report.push_str(&format!(
+                "Missing license texts in {}/: {}\n",
+                LICENSES_DIR_BASENAME,
+                render_identifiers(&self.missing_licenses)
+            ));
+        }
+        if !self.unused_licenses.is_empty() {
+            rendered_report.push_str(&format!(
+                "Unused license texts in {}/: {}\n",
+                LICENSES_DIR_BASENAME,
+                render_identifiers(&self.unused_licenses)
+            ));
+        }
+        for (title, files) in [
+            (
+                "Files without copyright information",
+                &self.files_without_copyright,
+            ),
+            (
+                "Files without license information",
+                &self.files_without_license,
+            ),
+        ] {
+            if !files.is_empty() {
+                rendered_report.push_str(&format!("{}:\n", title));
+                for file in files {
+                    rendered_report
+                        .push_str(&format!("    {}\n", display_relative_path(root, file)));
+                }
+            }
+        }
+        rendered_report
+    }
+}
+
+pub fn lint(
+    root: &Path,
+    exclude_globs: &[String],
+    use_cache: bool,
+) -> Result<LintReport, Box<dyn Error>> {
+    let file_scans = scan_files(root, exclude_globs, use_cache)?;
+    let annotations = Annotations::load(root)?;
+    let mut used_license_identifiers = BTreeSet::new();
+    let mut files_without_copyright = Vec::new();
+    let mut files_without_license = Vec::new();
+    for (path, file_scan) in find_covered_file_scans(root, &file_scans, &annotations) {
+        for expression in file_scan.license_identifiers() {
+            used_license_identifiers.extend(find_expression_identifiers(expression));
+        }
+        if file_scan.copyright_notices().is_empty() {
+            files_without_copyright.push(path.clone());
+        }
+        if file_scan.license_identifiers().is_empty() {
+            files_without_license.push(path.clone());
+        }
+    }
+    let provided_license_identifiers = find_provided_license_identifiers(root)?;
+    Ok(LintReport {
+        missing_licenses: used_license_identifiers
+            .difference(&provided_license_identifiers)
+            .cloned()
+            .collect(),
+        unused_licenses: provided_license_identifiers
+            .difference(&used_license_identifiers)
+            .cloned()
+            .collect(),
+        files_without_copyright,
+        files_without_license,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_project_files;
+
+    #[test]
+    fn lint_compliant_project() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                ("LICENSE", "MIT License"),
+                ("LICENSES/MIT.txt", "MIT License"),
+                (
+                    "src/main.rs",
+                    "// SPDX-FileCopyrightText: 2024 Jane Doe\n// SPDX-License-Identifier: MIT\n",
+                ),
+                ("logo.png", "binary"),
+                (
+                    "logo.png.license",
+                    "SPDX-FileCopyrightText: 2024 Jane Doe\nSPDX-License-Identifier: MIT\n",
+                ),
+            ],
+        );
+        assert!(lint(temp_dir.path(), &[], false).unwrap().is_compliant());
+    }
+
+    #[test]
+    fn lint_noncompliant_project() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                ("LICENSES/Apache-2.0.txt", "Apache License"),
+                (
+                    "src/main.rs",
+                    "// SPDX-License-Identifier: MIT OR GPL-3.0-or-later\n",
+                ),
+                ("src/lib.rs", "// Copyright 2024 Jane Doe\n"),
+            ],
+        );
+        let lint_report = lint(temp_dir.path(), &[], false).unwrap();
+        assert!(!lint_report.is_compliant());
+        assert_eq!(
+            lint_report.render(temp_dir.path()),
+            "\
+Missing license texts in LICENSES/: GPL-3.0-or-later, MIT
+Unused license texts in LICENSES/: Apache-2.0
+Files without copyright information:
+    src/main.rs
+Files without license information:
+    src/lib.rs
+"
+        );
+    }
+}
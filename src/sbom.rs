@@ -0,0 +1,883 @@
+use clap::ValueEnum;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::consistency::{find_license_files, normalize_license_identifier};
+use crate::core::Licenses;
+use crate::deps::Dependency;
+use crate::manifests::{escape_xml, find_manifests, read_manifest_license, DeclaredLicense};
+use crate::spdx::{find_expression_identifiers, parse_expression, Expression};
+
+const SPDX_VERSION: &str = "SPDX-2.3";
+const SPDX_DATA_LICENSE: &str = "CC0-1.0";
+const SPDX_DOCUMENT_ID: &str = "SPDXRef-DOCUMENT";
+const SPDX_PROJECT_PACKAGE_ID: &str = "SPDXRef-Project";
+const SPDX_NAMESPACE_PREFIX: &str = "https://spdx.org/spdxdocs";
+// Used by SPDX for unknown values.
+const SPDX_NO_ASSERTION: &str = "NOASSERTION";
+const CYCLONEDX_SPEC_VERSION: &str = "1.5";
+const CYCLONEDX_XML_NAMESPACE: &str = "http://cyclonedx.org/schema/bom/1.5";
+// SPDX identifiers of common licenses of dependencies that are missing from the catalog.
+const OTHER_SPDX_LICENSE_IDS: [&str; 34] = [
+    "0BSD",
+    "AFL-3.0",
+    "Apache-1.1",
+    "Artistic-2.0",
+    "BlueOak-1.0.0",
+    "BSD-1-Clause",
+    "BSD-2-Clause-Patent",
+    "BSD-3-Clause-Clear",
+    "CC-BY-4.0",
+    "CC-BY-SA-4.0",
+    "CDDL-1.0",
+    "CDDL-1.1",
+    "EPL-1.0",
+    "EUPL-1.2",
+    "ISC",
+    "LGPL-2.0-only",
+    "LGPL-3.0-only",
+    "MIT-0",
+    "MPL-1.1",
+    "MS-PL",
+    "NCSA",
+    "OFL-1.1",
+    "OpenSSL",
+    "PostgreSQL",
+    "PSF-2.0",
+    "Python-2.0",
+    "Ruby",
+    "Unicode-3.0",
+    "Unicode-DFS-2016",
+    "UPL-1.0",
+    "WTFPL",
+    "X11",
+    "Zlib",
+    "Zlib-Acknowledgement",
+];
+// SPDX identifiers of common license exceptions.
+const SPDX_EXCEPTION_IDS: [&str; 10] = [
+    "Autoconf-exception-3.0",
+    "Bison-exception-2.2",
+    "Classpath-exception-2.0",
+    "Font-exception-2.0",
+    "GCC-exception-3.1",
+    "Linux-syscall-note",
+    "LLVM-exception",
+    "OpenJDK-assembly-exception-1.0",
+    "Qt-LGPL-exception-1.1",
+    "WxWindows-exception-3.1",
+];
+
+/// Format of the SBOM document
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum SbomFormat {
+    /// SPDX 2.3 JSON
+    #[default]
+    SpdxJson,
+    /// SPDX 2.3 tag-value
+    SpdxTv,
+    /// CycloneDX 1.5 JSON
+    #[value(name = "cyclonedx-json")]
+    CycloneDxJson,
+    /// CycloneDX 1.5 XML
+    #[value(name = "cyclonedx-xml")]
+    CycloneDxXml,
+}
+
+impl SbomFormat {
+    pub fn is_cyclonedx(&self) -> bool {
+        matches!(self, SbomFormat::CycloneDxJson | SbomFormat::CycloneDxXml)
+    }
+}
+
+enum CycloneDxLicense<'a> {
+    Id(&'a str),
+    Name(&'a str),
+    Expression(&'a str),
+}
+
+struct SbomPackage {
+    spdx_id: String,
+    name: String,
+    version: Option<String>,
+    purl: Option<String>,
+    // SPDX expressions, `None` if unknown.
+    declared_license: Option<String>,
+    concluded_license: Option<String>,
+    // The same expressions as spelled in the SPDX license list, `None` if an identifier isn't
+    // listed there.
+    spdx_declared_license: Option<String>,
+    spdx_concluded_license: Option<String>,
+    // Text of the license from the catalog, if requested and the license is a single one.
+    license_text: Option<String>,
+    // Unique among the components of a CycloneDX document.
+    bom_ref: String,
+}
+
+impl SbomPackage {
+    // The project is described with its concluded license, the dependencies with the declared one.
+    fn license(&self) -> Option<&str> {
+        self.concluded_license
+            .as_deref()
+            .or(self.declared_license.as_deref())
+    }
+
+    fn spdx_license(&self) -> Option<&str> {
+        match self.concluded_license {
+            Some(_) => self.spdx_concluded_license.as_deref(),
+            None => self.spdx_declared_license.as_deref(),
+        }
+    }
+
+    // CycloneDX lists a single license by SPDX identifier, combinations of licenses as SPDX
+    // expression, and any license with an identifier missing from the SPDX license list by name.
+    fn cyclonedx_license(&self) -> Option<CycloneDxLicense<'_>> {
+        let license = self.license()?;
+        Some(match self.spdx_license() {
+            Some(spdx_license) => match parse_expression(spdx_license) {
+                Ok(Expression::License(_)) => CycloneDxLicense::Id(spdx_license),
+                _ => CycloneDxLicense::Expression(spdx_license),
+            },
+            None => CycloneDxLicense::Name(license),
+        })
+    }
+
+    fn cyclonedx_bom_ref(&self) -> &str {
+        &self.bom_ref
+    }
+}
+
+// Returns the identifier as spelled in the SPDX license list, if it is a known one. The GNU
+// licenses are known with their `-only` and `-or-later` suffixes, and their deprecated forms.
+fn find_known_spdx_id(licenses: &Licenses, identifier: &str) -> Option<String> {
+    licenses
+        .licenses()
+        .iter()
+        .map(|license| license.spdx_id())
+        .chain(OTHER_SPDX_LICENSE_IDS)
+        .flat_map(|spdx_id| match spdx_id.strip_suffix("-only") {
+            Some(base_spdx_id) => vec![
+                spdx_id.to_string(),
+                format!("{}-or-later", base_spdx_id),
+                base_spdx_id.to_string(),
+                format!("{}+", base_spdx_id),
+            ],
+            None => vec![spdx_id.to_string()],
+        })
+        .find(|spdx_id| spdx_id.eq_ignore_ascii_case(identifier))
+}
+
+// Renders the expression with the identifiers as spelled in the SPDX license list, or returns
+// `None` if one of them isn't listed there, as SPDX documents only allow listed identifiers.
+fn render_spdx_expression(licenses: &Licenses, expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::License(identifier) => find_known_spdx_id(licenses, identifier),
+        Expression::With(license, exception) => {
+            let exception = SPDX_EXCEPTION_IDS
+                .iter()
+                .find(|exception_id| exception_id.eq_ignore_ascii_case(exception))?;
+            Some(format!(
+                "{} WITH {}",
+                render_spdx_expression(licenses, license)?,
+                exception
+            ))
+        }
+        Expression::And(operands) => operands
+            .iter()
+            .map(|operand| {
+                let rendered_operand = render_spdx_expression(licenses, operand)?;
+                Some(match operand {
+                    Expression::Or(_) => format!("({})", rendered_operand),
+                    _ => rendered_operand,
+                })
+            })
+            .collect::<Option<Vec<String>>>()
+            .map(|rendered_operands| rendered_operands.join(" AND ")),
+        Expression::Or(operands) => operands
+            .iter()
+            .map(|operand| render_spdx_expression(licenses, operand))
+            .collect::<Option<Vec<String>>>()
+            .map(|rendered_operands| rendered_operands.join(" OR ")),
+    }
+}
+
+fn find_spdx_expression(licenses: &Licenses, expression: Option<&str>) -> Option<String> {
+    render_spdx_expression(licenses, &parse_expression(expression?).ok()?)
+}
+
+pub struct SbomDocument {
+    project: SbomPackage,
+    dependencies: Vec<SbomPackage>,
+    // Creation time, as an RFC 3339 UTC timestamp.
+    created: String,
+}
+
+// The concluded license of the project is the declared one if it agrees with the license files,
+// and the licenses detected in the license files otherwise.
+fn create_project_package(root: &Path, licenses: &Licenses) -> Result<SbomPackage, Box<dyn Error>> {
+    let name = root
+        .canonicalize()?
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut detected_license_identifiers: Vec<String> = Vec::new();
+    for license_file in find_license_files(root)? {
+        let license_text = String::from_utf8_lossy(&fs::read(license_file)?).to_string();
+        if let Some(license) = licenses.detect_license(&license_text) {
+            if !detected_license_identifiers.contains(&license.spdx_id().to_string()) {
+                detected_license_identifiers.push(license.spdx_id().to_string());
+            }
+        }
+    }
+    let mut declared_license = None;
+    for (manifest_kind, manifest_path) in find_manifests(root)? {
+        if let Some(DeclaredLicense::Expression(expression)) =
+            read_manifest_license(manifest_kind, &manifest_path, licenses)?
+        {
+            if parse_expression(&expression).is_ok() {
+                declared_license = Some(expression);
+                break;
+            }
+        }
+    }
+    let declared_license_identifiers: BTreeSet<String> = declared_license
+        .as_deref()
+        .map(find_expression_identifiers)
+        .unwrap_or_default()
+        .iter()
+        .map(|identifier| normalize_license_identifier(licenses, identifier))
+        .collect();
+    let concluded_license = if !detected_license_identifiers.is_empty()
+        && declared_license_identifiers == detected_license_identifiers.iter().cloned().collect()
+    {
+        declared_license.clone()
+    } else if !detected_license_identifiers.is_empty() {
+        Some(detected_license_identifiers.join(" AND "))
+    } else {
+        None
+    };
+    Ok(SbomPackage {
+        spdx_id: SPDX_PROJECT_PACKAGE_ID.to_string(),
+        name,
+        version: None,
+        purl: None,
+        declared_license,
+        concluded_license,
+        spdx_declared_license: None,
+        spdx_concluded_license: None,
+        license_text: None,
+        bom_ref: SPDX_PROJECT_PACKAGE_ID.to_string(),
+    })
+}
+
+// Only valid SPDX expressions are declared, the license of the dependencies isn't concluded.
+fn create_dependency_package(index: usize, dependency: &Dependency) -> SbomPackage {
+    let declared_license = match dependency.declared_license() {
+        Some(DeclaredLicense::Expression(expression)) if parse_expression(expression).is_ok() => {
+            Some(expression.to_string())
+        }
+        _ => None,
+    };
+    SbomPackage {
+        spdx_id: format!("SPDXRef-Package-{}", index + 1),
+        name: dependency.name().to_string(),
+        version: Some(dependency.version().to_string()),
+        purl: Some(dependency.render_purl()),
+        declared_license,
+        concluded_license: None,
+        spdx_declared_license: None,
+        spdx_concluded_license: None,
+        license_text: None,
+        bom_ref: dependency.render_purl(),
+    }
+}
+
+impl SbomDocument {
+    pub fn new(
+        root: &Path,
+        dependencies: &[Dependency],
+        licenses: &Licenses,
+        created: &str,
+        embeds_license_texts: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut sbom_document = SbomDocument {
+            project: create_project_package(root, licenses)?,
+            dependencies: dependencies
+                .iter()
+                .enumerate()
+                .map(|(index, dependency)| create_dependency_package(index, dependency))
+                .collect(),
+            created: created.to_string(),
+        };
+        // The same package may come from several sources, e.g. a registry and a git repository,
+        // so the SPDX identifier tells their purls apart.
+        let mut bom_refs = BTreeSet::new();
+        for dependency in &mut sbom_document.dependencies {
+            if !bom_refs.insert(dependency.bom_ref.clone()) {
+                dependency.bom_ref = format!("{}#{}", dependency.bom_ref, dependency.spdx_id);
+            }
+        }
+        for package in
+            std::iter::once(&mut sbom_document.project).chain(&mut sbom_document.dependencies)
+        {
+            package.spdx_declared_license =
+                find_spdx_expression(licenses, package.declared_license.as_deref());
+            package.spdx_concluded_license =
+                find_spdx_expression(licenses, package.concluded_license.as_deref());
+            if embeds_license_texts {
+                package.license_text = match package.cyclonedx_license() {
+                    Some(CycloneDxLicense::Id(spdx_id)) => licenses
+                        .find_license_by_spdx_id(spdx_id)
+                        .map(|license| license.fetch_template().to_string()),
+                    _ => None,
+                };
+            }
+        }
+        Ok(sbom_document)
+    }
+
+    fn packages(&self) -> Vec<&SbomPackage> {
+        std::iter::once(&self.project)
+            .chain(&self.dependencies)
+            .collect()
+    }
+
+    // SPDX requires a unique URI per document, derived here from the project and the time.
+    fn spdx_namespace(&self) -> String {
+        format!(
+            "{}/{}-{}",
+            SPDX_NAMESPACE_PREFIX,
+            self.project.name,
+            self.created.replace([':', '-'], "")
+        )
+    }
+
+    fn spdx_creator(&self) -> String {
+        format!("Tool: licensit-{}", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn render_spdx_json(&self) -> Result<String, Box<dyn Error>> {
+        let packages: Vec<serde_json::Value> = self
+            .packages()
+            .into_iter()
+            .map(|package| {
+                let mut spdx_package = json!({
+                    "SPDXID": package.spdx_id,
+                    "name": package.name,
+                    "downloadLocation": SPDX_NO_ASSERTION,
+                    "filesAnalyzed": false,
+                    "licenseConcluded": package.spdx_concluded_license.as_deref().unwrap_or(SPDX_NO_ASSERTION),
+                    "licenseDeclared": package.spdx_declared_license.as_deref().unwrap_or(SPDX_NO_ASSERTION),
+                    "copyrightText": SPDX_NO_ASSERTION,
+                });
+                if let Some(version) = &package.version {
+                    spdx_package["versionInfo"] = json!(version);
+                }
+                if let Some(purl) = &package.purl {
+                    spdx_package["externalRefs"] = json!([{
+                        "referenceCategory": "PACKAGE-MANAGER",
+                        "referenceType": "purl",
+                        "referenceLocator": purl,
+                    }]);
+                }
+                spdx_package
+            })
+            .collect();
+        let mut relationships = vec![json!({
+            "spdxElementId": SPDX_DOCUMENT_ID,
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": self.project.spdx_id,
+        })];
+        for dependency in &self.dependencies {
+            relationships.push(json!({
+                "spdxElementId": self.project.spdx_id,
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": dependency.spdx_id,
+            }));
+        }
+        let spdx_document = json!({
+            "spdxVersion": SPDX_VERSION,
+            "dataLicense": SPDX_DATA_LICENSE,
+            "SPDXID": SPDX_DOCUMENT_ID,
+            "name": self.project.name,
+            "documentNamespace": self.spdx_namespace(),
+            "creationInfo": {
+                "created": self.created,
+                "creators": [self.spdx_creator()],
+            },
+            "packages": packages,
+            "relationships": relationships,
+        });
+        Ok(format!(
+            "{}\n",
+            serde_json::to_string_pretty(&spdx_document)?
+        ))
+    }
+
+    fn render_spdx_tag_value(&self) -> String {
+        let mut rendered_document = format!(
+            "SPDXVersion: {}\nDataLicense: {}\nSPDXID: {}\nDocumentName: {}\nDocumentNamespace: {}\nCreator: {}\nCreated: {}\n",
+            SPDX_VERSION,
+            SPDX_DATA_LICENSE,
+            SPDX_DOCUMENT_ID,
+            self.project.name,
+            self.spdx_namespace(),
+            self.spdx_creator(),
+            self.created
+        );
+        for package in self.packages() {
+            rendered_document.push_str(&format!(
+                "\nPackageName: {}\nSPDXID: {}\n",
+                package.name, package.spdx_id
+            ));
+            if let Some(version) = &package.version {
+                rendered_document.push_str(&format!("PackageVersion: {}\n", version));
+            }
+            rendered_document.push_str(&format!(
+                "PackageDownloadLocation: {}\nFilesAnalyzed: false\nPackageLicenseConcluded: {}\nPackageLicenseDeclared: {}\nPackageCopyrightText: {}\n",
+                SPDX_NO_ASSERTION,
+                package.spdx_concluded_license.as_deref().unwrap_or(SPDX_NO_ASSERTION),
+                package.spdx_declared_license.as_deref().unwrap_or(SPDX_NO_ASSERTION),
+                SPDX_NO_ASSERTION
+            ));
+            if let Some(purl) = &package.purl {
+                rendered_document
+                    .push_str(&format!("ExternalRef: PACKAGE-MANAGER purl {}\n", purl));
+            }
+        }
+        rendered_document.push_str(&format!(
+            "\nRelationship: {} DESCRIBES {}\n",
+            SPDX_DOCUMENT_ID, self.project.spdx_id
+        ));
+        for dependency in &self.dependencies {
+            rendered_document.push_str(&format!(
+                "Relationship: {} DEPENDS_ON {}\n",
+                self.project.spdx_id, dependency.spdx_id
+            ));
+        }
+        rendered_document
+    }
+
+    fn render_cyclonedx_json_component(
+        &self,
+        package: &SbomPackage,
+        component_type: &str,
+    ) -> serde_json::Value {
+        let mut component = json!({
+            "type": component_type,
+            "bom-ref": package.cyclonedx_bom_ref(),
+            "name": package.name,
+        });
+        if let Some(version) = &package.version {
+            component["version"] = json!(version);
+        }
+        let license = match package.cyclonedx_license() {
+            Some(CycloneDxLicense::Id(spdx_id)) => Some(json!({ "id": spdx_id })),
+            Some(CycloneDxLicense::Name(name)) => Some(json!({ "name": name })),
+            Some(CycloneDxLicense::Expression(expression)) => {
+                component["licenses"] = json!([{ "expression": expression }]);
+                None
+            }
+            None => None,
+        };
+        if let Some(mut license) = license {
+            if let Some(license_text) = &package.license_text {
+                license["text"] = json!({
+                    "contentType": "text/plain",
+                    "content": license_text,
+                });
+            }
+            component["licenses"] = json!([{ "license": license }]);
+        }
+        if let Some(purl) = &package.purl {
+            component["purl"] = json!(purl);
+        }
+        component
+    }
+
+    fn render_cyclonedx_json(&self) -> Result<String, Box<dyn Error>> {
+        let components: Vec<serde_json::Value> = self
+            .dependencies
+            .iter()
+            .map(|dependency| self.render_cyclonedx_json_component(dependency, "library"))
+            .collect();
+        let cyclonedx_document = json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": CYCLONEDX_SPEC_VERSION,
+            "version": 1,
+            "metadata": {
+                "timestamp": self.created,
+                "tools": [{ "name": "licensit", "version": env!("CARGO_PKG_VERSION") }],
+                "component": self.render_cyclonedx_json_component(&self.project, "application"),
+            },
+            "components": components,
+            "dependencies": [{
+                "ref": self.project.cyclonedx_bom_ref(),
+                "dependsOn": self
+                    .dependencies
+                    .iter()
+                    .map(|dependency| dependency.cyclonedx_bom_ref())
+                    .collect::<Vec<&str>>(),
+            }],
+        });
+        Ok(format!(
+            "{}\n",
+            serde_json::to_string_pretty(&cyclonedx_document)?
+        ))
+    }
+
+    fn render_cyclonedx_xml_component(
+        &self,
+        package: &SbomPackage,
+        component_type: &str,
+        indent: &str,
+    ) -> String {
+        let escape_xml_attribute = |text: &str| escape_xml(text).replace('"', "&quot;");
+        let mut rendered_component = format!(
+            "{indent}<component type=\"{}\" bom-ref=\"{}\">\n{indent}  <name>{}</name>\n",
+            component_type,
+            escape_xml_attribute(package.cyclonedx_bom_ref()),
+            escape_xml(&package.name),
+        );
+        if let Some(version) = &package.version {
+            rendered_component.push_str(&format!(
+                "{indent}  <version>{}</version>\n",
+                escape_xml(version)
+            ));
+        }
+        let license = match package.cyclonedx_license() {
+            Some(CycloneDxLicense::Id(spdx_id)) => Some(("id", spdx_id)),
+            Some(CycloneDxLicense::Name(name)) => Some(("name", name)),
+            Some(CycloneDxLicense::Expression(expression)) => {
+                rendered_component.push_str(&format!(
+                    "{indent}  <licenses>\n{indent}    <expression>{}</expression>\n{indent}  </licenses>\n",
+                    escape_xml(expression)
+                ));
+                None
+            }
+            None => None,
+        };
+        if let Some((tag, value)) = license {
+            rendered_component.push_str(&format!(
+                "{indent}  <licenses>\n{indent}    <license>\n{indent}      <{tag}>{}</{tag}>\n",
+                escape_xml(value)
+            ));
+            if let Some(license_text) = &package.license_text {
+                rendered_component.push_str(&format!(
+                    "{indent}      <text content-type=\"text/plain\">{}</text>\n",
+                    escape_xml(license_text)
+                ));
+            }
+            rendered_component
+                .push_str(&format!("{indent}    </license>\n{indent}  </licenses>\n"));
+        }
+        if let Some(purl) = &package.purl {
+            rendered_component.push_str(&format!("{indent}  <purl>{}</purl>\n", escape_xml(purl)));
+        }
+        rendered_component.push_str(&format!("{indent}</component>\n"));
+        rendered_component
+    }
+
+    fn render_cyclonedx_xml(&self) -> String {
+        let escape_xml_attribute = |text: &str| escape_xml(text).replace('"', "&quot;");
+        let mut rendered_document = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<bom xmlns=\"{}\" version=\"1\">\n  <metadata>\n    <timestamp>{}</timestamp>\n    <tools>\n      <tool>\n        <name>licensit</name>\n        <version>{}</version>\n      </tool>\n    </tools>\n",
+            CYCLONEDX_XML_NAMESPACE,
+            self.created,
+            env!("CARGO_PKG_VERSION")
+        );
+        rendered_document.push_str(&self.render_cyclonedx_xml_component(
+            &self.project,
+            "application",
+            "    ",
+        ));
+        rendered_document.push_str("  </metadata>\n  <components>\n");
+        for dependency in &self.dependencies {
+            rendered_document
+                .push_str(&self.render_cyclonedx_xml_component(dependency, "library", "    "));
+        }
+        rendered_document.push_str(&format!(
+            "  </components>\n  <dependencies>\n    <dependency ref=\"{}\">\n",
+            escape_xml_attribute(self.project.cyclonedx_bom_ref())
+        ));
+        for dependency in &self.dependencies {
+            rendered_document.push_str(&format!(
+                "      <dependency ref=\"{}\"/>\n",
+                escape_xml_attribute(dependency.cyclonedx_bom_ref())
+            ));
+        }
+        rendered_document.push_str("    </dependency>\n  </dependencies>\n</bom>\n");
+        rendered_document
+    }
+
+    pub fn render(&self, format: SbomFormat) -> Result<String, Box<dyn Error>> {
+        match format {
+            SbomFormat::SpdxJson => self.render_spdx_json(),
+            SbomFormat::SpdxTv => Ok(self.render_spdx_tag_value()),
+            SbomFormat::CycloneDxJson => self.render_cyclonedx_json(),
+            SbomFormat::CycloneDxXml => Ok(self.render_cyclonedx_xml()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deps::collect_dependencies;
+    use crate::test_utils::create_project_files;
+
+    const CREATED: &str = "2024-05-01T12:00:00Z";
+
+    fn create_test_document(
+        licenses: &Licenses,
+        embeds_license_texts: bool,
+    ) -> (tempfile::TempDir, SbomDocument) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("app");
+        create_project_files(
+            &root,
+            &[
+                ("LICENSE", licenses.find_license("mit").unwrap().fetch_template()),
+                ("Cargo.toml", "[package]\nname = \"app\"\nlicense = \"MIT\"\n"),
+                (
+                    "Cargo.lock",
+                    "version = 3\n\n[[package]]\nname = \"either\"\nversion = \"1.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"custom\"\nversion = \"0.1.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+                ),
+                (
+                    "vendor/either/Cargo.toml",
+                    "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+                ),
+                (
+                    "vendor/custom/Cargo.toml",
+                    "[package]\nname = \"custom\"\nversion = \"0.1.0\"\nlicense-file = \"LICENSE\"\n",
+                ),
+            ],
+        );
+        let dependencies = collect_dependencies(&root, None, licenses).unwrap();
+        let sbom_document = SbomDocument::new(
+            &root,
+            &dependencies,
+            licenses,
+            CREATED,
+            embeds_license_texts,
+        )
+        .unwrap();
+        (temp_dir, sbom_document)
+    }
+
+    #[test]
+    fn render_spdx_tag_value_document() {
+        let licenses = Licenses::from_description_file();
+        let (_temp_dir, sbom_document) = create_test_document(&licenses, false);
+        let rendered_document = sbom_document.render(SbomFormat::SpdxTv).unwrap();
+        assert!(rendered_document.starts_with(
+            "SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\nSPDXID: SPDXRef-DOCUMENT\nDocumentName: app\nDocumentNamespace: https://spdx.org/spdxdocs/app-20240501T120000Z\n"
+        ));
+        assert!(rendered_document.contains(
+            "PackageName: app\nSPDXID: SPDXRef-Project\nPackageDownloadLocation: NOASSERTION\nFilesAnalyzed: false\nPackageLicenseConcluded: MIT\nPackageLicenseDeclared: MIT\n"
+        ));
+        assert!(rendered_document.contains(
+            "PackageName: either\nSPDXID: SPDXRef-Package-1\nPackageVersion: 1.9.0\nPackageDownloadLocation: NOASSERTION\nFilesAnalyzed: false\nPackageLicenseConcluded: NOASSERTION\nPackageLicenseDeclared: MIT OR Apache-2.0\nPackageCopyrightText: NOASSERTION\nExternalRef: PACKAGE-MANAGER purl pkg:cargo/either@1.9.0\n"
+        ));
+        assert!(rendered_document.contains("PackageLicenseDeclared: NOASSERTION\n"));
+        assert!(rendered_document.ends_with(
+            "Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-Project\nRelationship: SPDXRef-Project DEPENDS_ON SPDXRef-Package-1\nRelationship: SPDXRef-Project DEPENDS_ON SPDXRef-Package-2\n"
+        ));
+    }
+
+    #[test]
+    fn render_spdx_json_document() {
+        let licenses = Licenses::from_description_file();
+        let (_temp_dir, sbom_document) = create_test_document(&licenses, false);
+        let rendered_document = sbom_document.render(SbomFormat::SpdxJson).unwrap();
+        let spdx_document: serde_json::Value = serde_json::from_str(&rendered_document).unwrap();
+        assert_eq!(spdx_document["spdxVersion"], "SPDX-2.3");
+        assert_eq!(spdx_document["creationInfo"]["created"], CREATED);
+        assert_eq!(spdx_document["packages"][0]["licenseConcluded"], "MIT");
+        assert_eq!(
+            spdx_document["packages"][1]["externalRefs"][0]["referenceLocator"],
+            "pkg:cargo/either@1.9.0"
+        );
+        assert_eq!(
+            spdx_document["relationships"][2]["relatedSpdxElement"],
+            "SPDXRef-Package-2"
+        );
+    }
+
+    #[test]
+    fn render_spdx_tag_value_document_with_non_spdx_licenses() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("app");
+        create_project_files(
+            &root,
+            &[
+                ("Cargo.toml", "[package]\nname = \"app\"\nlicense = \"BSD\"\n"),
+                (
+                    "Cargo.lock",
+                    "version = 3\n\n[[package]]\nname = \"legacy\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"llvm\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+                ),
+                (
+                    "vendor/legacy/Cargo.toml",
+                    "[package]\nname = \"legacy\"\nversion = \"1.0.0\"\nlicense = \"mit OR BSD\"\n",
+                ),
+                (
+                    "vendor/llvm/Cargo.toml",
+                    "[package]\nname = \"llvm\"\nversion = \"1.0.0\"\nlicense = \"(mit OR isc) AND apache-2.0 WITH llvm-exception\"\n",
+                ),
+            ],
+        );
+        let dependencies = collect_dependencies(&root, None, &licenses).unwrap();
+        let sbom_document =
+            SbomDocument::new(&root, &dependencies, &licenses, CREATED, false).unwrap();
+        let rendered_document = sbom_document.render(SbomFormat::SpdxTv).unwrap();
+        assert!(rendered_document.contains(
+            "PackageName: app\nSPDXID: SPDXRef-Project\nPackageDownloadLocation: NOASSERTION\nFilesAnalyzed: false\nPackageLicenseConcluded: NOASSERTION\nPackageLicenseDeclared: NOASSERTION\n"
+        ));
+        assert!(rendered_document.contains(
+            "PackageName: legacy\nSPDXID: SPDXRef-Package-1\nPackageVersion: 1.0.0\nPackageDownloadLocation: NOASSERTION\nFilesAnalyzed: false\nPackageLicenseConcluded: NOASSERTION\nPackageLicenseDeclared: NOASSERTION\n"
+        ));
+        assert!(rendered_document
+            .contains("PackageLicenseDeclared: (MIT OR ISC) AND Apache-2.0 WITH LLVM-exception\n"));
+    }
+
+    #[test]
+    fn render_cyclonedx_json_document_with_license_texts() {
+        let licenses = Licenses::from_description_file();
+        let (_temp_dir, sbom_document) = create_test_document(&licenses, true);
+        let rendered_document = sbom_document.render(SbomFormat::CycloneDxJson).unwrap();
+        let cyclonedx_document: serde_json::Value =
+            serde_json::from_str(&rendered_document).unwrap();
+        assert_eq!(cyclonedx_document["bomFormat"], "CycloneDX");
+        let project_license = &cyclonedx_document["metadata"]["component"]["licenses"][0];
+        assert_eq!(project_license["license"]["id"], "MIT");
+        assert_eq!(
+            project_license["license"]["text"]["content"],
+            licenses.find_license("mit").unwrap().fetch_template()
+        );
+        assert_eq!(
+            cyclonedx_document["components"][0]["licenses"][0]["expression"],
+            "MIT OR Apache-2.0"
+        );
+        assert!(cyclonedx_document["components"][1]["licenses"].is_null());
+        assert_eq!(
+            cyclonedx_document["dependencies"][0]["dependsOn"][1],
+            "pkg:cargo/custom@0.1.0"
+        );
+    }
+
+    #[test]
+    fn render_cyclonedx_json_document_with_unknown_licenses_and_duplicate_purls() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("app");
+        create_project_files(
+            &root,
+            &[
+                (
+                    "Cargo.toml",
+                    "[package]\nname = \"app\"\nlicense = \"LicenseRef-Proprietary\"\n",
+                ),
+                (
+                    "Cargo.lock",
+                    "version = 3\n\n[[package]]\nname = \"legacy\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"legacy\"\nversion = \"1.0.0\"\nsource = \"git+https://github.com/example/legacy#0123456789abcdef\"\n\n[[package]]\nname = \"ring\"\nversion = \"0.17.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+                ),
+                (
+                    "vendor/legacy/Cargo.toml",
+                    "[package]\nname = \"legacy\"\nversion = \"1.0.0\"\nlicense = \"BSD\"\n",
+                ),
+                (
+                    "vendor/ring/Cargo.toml",
+                    "[package]\nname = \"ring\"\nversion = \"0.17.0\"\nlicense = \"isc\"\n",
+                ),
+            ],
+        );
+        let dependencies = collect_dependencies(&root, None, &licenses).unwrap();
+        let sbom_document =
+            SbomDocument::new(&root, &dependencies, &licenses, CREATED, false).unwrap();
+        let rendered_document = sbom_document.render(SbomFormat::CycloneDxJson).unwrap();
+        let cyclonedx_document: serde_json::Value =
+            serde_json::from_str(&rendered_document).unwrap();
+        assert_eq!(
+            cyclonedx_document["metadata"]["component"]["licenses"][0]["license"],
+            json!({ "name": "LicenseRef-Proprietary" })
+        );
+        let components = &cyclonedx_document["components"];
+        assert_eq!(
+            components[0]["licenses"][0]["license"],
+            json!({ "name": "BSD" })
+        );
+        assert_eq!(components[0]["bom-ref"], "pkg:cargo/legacy@1.0.0");
+        assert_eq!(
+            components[1]["bom-ref"],
+            "pkg:cargo/legacy@1.0.0#SPDXRef-Package-2"
+        );
+        assert_eq!(
+            components[2]["licenses"][0]["license"],
+            json!({ "id": "ISC" })
+        );
+        assert_eq!(
+            cyclonedx_document["dependencies"][0]["dependsOn"][1],
+            "pkg:cargo/legacy@1.0.0#SPDXRef-Package-2"
+        );
+    }
+
+    #[test]
+    fn render_cyclonedx_json_document_with_non_spdx_expressions() {
+        let licenses = Licenses::from_description_file();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("app");
+        create_project_files(
+            &root,
+            &[
+                ("Cargo.toml", "[package]\nname = \"app\"\nlicense = \"MIT\"\n"),
+                (
+                    "Cargo.lock",
+                    "version = 3\n\n[[package]]\nname = \"legacy\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"llvm\"\nversion = \"1.0.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+                ),
+                (
+                    "vendor/legacy/Cargo.toml",
+                    "[package]\nname = \"legacy\"\nversion = \"1.0.0\"\nlicense = \"mit OR BSD\"\n",
+                ),
+                (
+                    "vendor/llvm/Cargo.toml",
+                    "[package]\nname = \"llvm\"\nversion = \"1.0.0\"\nlicense = \"(mit OR isc) AND apache-2.0 WITH llvm-exception\"\n",
+                ),
+            ],
+        );
+        let dependencies = collect_dependencies(&root, None, &licenses).unwrap();
+        let sbom_document =
+            SbomDocument::new(&root, &dependencies, &licenses, CREATED, false).unwrap();
+        let rendered_document = sbom_document.render(SbomFormat::CycloneDxJson).unwrap();
+        let cyclonedx_document: serde_json::Value =
+            serde_json::from_str(&rendered_document).unwrap();
+        let components = &cyclonedx_document["components"];
+        assert_eq!(
+            components[0]["licenses"],
+            json!([{ "license": { "name": "mit OR BSD" } }])
+        );
+        assert_eq!(
+            components[1]["licenses"],
+            json!([{ "expression": "(MIT OR ISC) AND Apache-2.0 WITH LLVM-exception" }])
+        );
+    }
+
+    #[test]
+    fn render_cyclonedx_xml_document() {
+        let licenses = Licenses::from_description_file();
+        let (_temp_dir, sbom_document) = create_test_document(&licenses, false);
+        let rendered_document = sbom_document.render(SbomFormat::CycloneDxXml).unwrap();
+        assert!(rendered_document.contains(
+            "    <component type=\"application\" bom-ref=\"SPDXRef-Project\">\n      <name>app</name>\n      <licenses>\n        <license>\n          <id>MIT</id>\n        </license>\n      </licenses>\n    </component>\n"
+        ));
+        assert!(rendered_document.contains(
+            "    <component type=\"library\" bom-ref=\"pkg:cargo/either@1.9.0\">\n      <name>either</name>\n      <version>1.9.0</version>\n      <licenses>\n        <expression>MIT OR Apache-2.0</expression>\n      </licenses>\n      <purl>pkg:cargo/either@1.9.0</purl>\n    </component>\n"
+        ));
+        assert!(rendered_document.ends_with(
+            "    <dependency ref=\"SPDXRef-Project\">\n      <dependency ref=\"pkg:cargo/either@1.9.0\"/>\n      <dependency ref=\"pkg:cargo/custom@0.1.0\"/>\n    </dependency>\n  </dependencies>\n</bom>\n"
+        ));
+    }
+}
@@ -0,0 +1,282 @@
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use crate::headers::{find_copyright_notices, find_license_identifiers};
+use crate::walk::{display_relative_path, visit_files_parallel};
+
+const SCAN_CACHE_DIR_BASENAME: &str = "licensit";
+// Bump whenever `FileScan` changes, so caches written by older versions are discarded.
+const SCAN_CACHE_VERSION: u32 = 2;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FileScan {
+    license_identifiers: Vec<String>,
+    copyright_notices: Vec<String>,
+}
+
+impl FileScan {
+    pub fn new(license_identifiers: Vec<String>, copyright_notices: Vec<String>) -> Self {
+        FileScan {
+            license_identifiers,
+            copyright_notices,
+        }
+    }
+
+    fn from_content(content: &[u8]) -> Self {
+        let content = String::from_utf8_lossy(content);
+        FileScan {
+            license_identifiers: find_license_identifiers(&content),
+            copyright_notices: find_copyright_notices(&content),
+        }
+    }
+
+    pub fn license_identifiers(&self) -> &Vec<String> {
+        &self.license_identifiers
+    }
+
+    pub fn copyright_notices(&self) -> &Vec<String> {
+        &self.copyright_notices
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ScanCacheEntry {
+    modified_secs: u64,
+    modified_nanos: u32,
+    size: u64,
+    scan: FileScan,
+}
+
+// Entries are keyed by the path relative to the scanned directory, so the cache survives
+// moving the whole project around.
+#[derive(Default, Serialize, Deserialize)]
+struct ScanCache {
+    version: u32,
+    entries: HashMap<String, ScanCacheEntry>,
+}
+
+impl ScanCache {
+    fn load(cache_file_path: &Path) -> Self {
+        // A missing, unreadable or outdated cache is not an error, all files are just scanned again.
+        fs::read(cache_file_path)
+            .ok()
+            .and_then(|cache_file_content| {
+                serde_json::from_slice::<ScanCache>(&cache_file_content).ok()
+            })
+            .filter(|cache| cache.version == SCAN_CACHE_VERSION)
+            .unwrap_or_default()
+    }
+
+    // Writes a temporary file next to the cache and renames it, so that an interrupted or
+    // concurrent run never leaves a truncated cache behind.
+    fn save(&self, cache_file_path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(cache_dir) = cache_file_path.parent() {
+            fs::create_dir_all(cache_dir)?;
+        }
+        let mut temp_file_path = cache_file_path.as_os_str().to_owned();
+        temp_file_path.push(format!(".{}.tmp", process::id()));
+        let temp_file_path = PathBuf::from(temp_file_path);
+        fs::write(&temp_file_path, serde_json::to_vec(self)?)?;
+        fs::rename(&temp_file_path, cache_file_path).map_err(|error| {
+            let _ = fs::remove_file(&temp_file_path);
+            error
+        })?;
+        Ok(())
+    }
+}
+
+// `$XDG_CACHE_HOME`, or `~/.cache`, `None` if neither is set.
+fn find_cache_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME")
+        .filter(|cache_home| !cache_home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+}
+
+// 64-bit FNV-1a, whose output, unlike the one of the hashers of the standard library, is the same
+// across Rust releases, so that upgrading the toolchain doesn't orphan the caches.
+fn hash_fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+// The cache of a scanned directory lives in the `licensit` directory of the cache directory, so
+// that scanning never writes into the scanned directory. It is named after a hash of the absolute
+// path of the directory.
+fn find_cache_file_path(cache_dir: &Path, root: &Path) -> Option<PathBuf> {
+    let absolute_root = fs::canonicalize(root).ok()?;
+    Some(cache_dir.join(SCAN_CACHE_DIR_BASENAME).join(format!(
+        "scan-{:016x}.json",
+        hash_fnv1a(absolute_root.to_string_lossy().as_bytes())
+    )))
+}
+
+// Walks and reads the files in parallel. With `use_cache`, files whose modification time and
+// size match the previous run are not read again.
+pub fn scan_files(
+    root: &Path,
+    exclude_globs: &[String],
+    use_cache: bool,
+) -> Result<BTreeMap<PathBuf, FileScan>, Box<dyn Error>> {
+    let cache_file_path_option = if use_cache {
+        find_cache_dir().and_then(|cache_dir| find_cache_file_path(&cache_dir, root))
+    } else {
+        None
+    };
+    scan_files_with_cache(root, exclude_globs, cache_file_path_option.as_deref())
+}
+
+fn scan_files_with_cache(
+    root: &Path,
+    exclude_globs: &[String],
+    cache_file_path_option: Option<&Path>,
+) -> Result<BTreeMap<PathBuf, FileScan>, Box<dyn Error>> {
+    let previous_cache = cache_file_path_option
+        .map(ScanCache::load)
+        .unwrap_or_default();
+    let scanned_files = Mutex::new(Vec::new());
+    visit_files_parallel(root, exclude_globs, |path| {
+        let metadata = fs::metadata(&path)?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let cache_key = display_relative_path(root, &path);
+        let cache_entry = match previous_cache.entries.get(&cache_key) {
+            Some(cached_entry)
+                if cached_entry.modified_secs == modified.as_secs()
+                    && cached_entry.modified_nanos == modified.subsec_nanos()
+                    && cached_entry.size == metadata.len() =>
+            {
+                cached_entry.clone()
+            }
+            _ => ScanCacheEntry {
+                modified_secs: modified.as_secs(),
+                modified_nanos: modified.subsec_nanos(),
+                size: metadata.len(),
+                scan: FileScan::from_content(&fs::read(&path)?),
+            },
+        };
+        scanned_files
+            .lock()
+            .unwrap()
+            .push((path, cache_key, cache_entry));
+        Ok(())
+    })?;
+    let mut current_cache = ScanCache {
+        version: SCAN_CACHE_VERSION,
+        entries: HashMap::new(),
+    };
+    let mut file_scans = BTreeMap::new();
+    for (path, cache_key, cache_entry) in scanned_files.into_inner().unwrap() {
+        file_scans.insert(path, cache_entry.scan.clone());
+        current_cache.entries.insert(cache_key, cache_entry);
+    }
+    // The cache is only an optimization, so failing to save it doesn't fail the scan.
+    if let Some(cache_file_path) = cache_file_path_option {
+        if let Err(error) = current_cache.save(cache_file_path) {
+            eprintln!(
+                "{}: could not save the scan cache to {}: {}",
+                "warning".yellow(),
+                cache_file_path.display(),
+                error
+            );
+        }
+    }
+    Ok(file_scans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_single_file(root: &Path, cache_file_path_option: Option<&Path>) -> FileScan {
+        let file_scans = scan_files_with_cache(root, &[], cache_file_path_option).unwrap();
+        assert_eq!(file_scans.len(), 1);
+        file_scans.into_values().next().unwrap()
+    }
+
+    fn tamper_cached_license_identifiers(cache_file_path: &Path, license_identifiers: Vec<String>) {
+        let mut cache = ScanCache::load(cache_file_path);
+        for cache_entry in cache.entries.values_mut() {
+            cache_entry.scan.license_identifiers = license_identifiers.clone();
+        }
+        cache.save(cache_file_path).unwrap();
+    }
+
+    #[test]
+    fn scan_files_reuses_cache_for_unchanged_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_file_path = cache_dir.path().join("licensit").join("scan.json");
+        fs::write(temp_dir.path().join("lib.rs"), "pub fn lib() {}\n").unwrap();
+        assert!(scan_single_file(temp_dir.path(), Some(&cache_file_path))
+            .license_identifiers()
+            .is_empty());
+        // The file is unchanged, so the tampered cache entry must be returned as is.
+        tamper_cached_license_identifiers(&cache_file_path, vec!["MIT".to_string()]);
+        assert_eq!(
+            scan_single_file(temp_dir.path(), Some(&cache_file_path)).license_identifiers(),
+            &vec!["MIT".to_string()]
+        );
+        // Nothing is written into the scanned directory.
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn scan_files_rescans_changed_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_file_path = cache_dir.path().join("scan.json");
+        let file_path = temp_dir.path().join("lib.rs");
+        fs::write(&file_path, "pub fn lib() {}\n").unwrap();
+        scan_single_file(temp_dir.path(), Some(&cache_file_path));
+        tamper_cached_license_identifiers(&cache_file_path, vec!["MIT".to_string()]);
+        fs::write(
+            &file_path,
+            "// SPDX-License-Identifier: 0BSD\npub fn lib() {}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            scan_single_file(temp_dir.path(), Some(&cache_file_path)).license_identifiers(),
+            &vec!["0BSD".to_string()]
+        );
+    }
+
+    #[test]
+    fn scan_files_with_unwritable_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(temp_dir.path().join("lib.rs"), "pub fn lib() {}\n").unwrap();
+        // The parent of the cache file is a file, so the cache can't be saved.
+        let cache_file_path = temp_dir.path().join("lib.rs").join("scan.json");
+        scan_single_file(temp_dir.path(), Some(&cache_file_path));
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn find_cache_file_path_outside_scanned_directory() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_file_path = find_cache_file_path(cache_dir.path(), temp_dir.path()).unwrap();
+        assert!(cache_file_path.starts_with(cache_dir.path().join(SCAN_CACHE_DIR_BASENAME)));
+        assert_eq!(
+            find_cache_file_path(cache_dir.path(), &temp_dir.path().join(".")),
+            Some(cache_file_path)
+        );
+    }
+
+    #[test]
+    fn hash_fnv1a_is_stable() {
+        assert_eq!(hash_fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(hash_fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
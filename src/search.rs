@@ -0,0 +1,151 @@
+use colored::Colorize;
+use regex::Regex;
+use std::ops::Range;
+
+use crate::core::License;
+
+// The matches of the pattern in the template of a license, as byte ranges of the template.
+pub struct LicenseMatches<'a> {
+    license: &'a License,
+    template: &'a str,
+    match_ranges: Vec<Range<usize>>,
+}
+
+impl LicenseMatches<'_> {
+    pub fn license(&self) -> &License {
+        self.license
+    }
+
+    pub fn matches_count(&self) -> usize {
+        self.match_ranges.len()
+    }
+
+    // Prints the lines of the template containing a match, with the matches highlighted, and
+    // `context_lines_count` lines around them. Like `grep`, line numbers are followed by `:` for
+    // matching lines and `-` for context lines, and non-adjacent groups are separated by `--`.
+    pub fn render(&self, context_lines_count: usize) -> String {
+        let mut rendered_matches = format!(
+            "{} ({})\n",
+            self.license.name().bold(),
+            self.license.full_name()
+        );
+        // The ranges of the lines without their newline, and the indexes of the lines with a match.
+        let mut line_ranges = Vec::new();
+        let mut matching_line_indexes = Vec::new();
+        let mut line_start = 0;
+        for (line_index, line) in self.template.split_inclusive('\n').enumerate() {
+            let line_end = line_start + line.len();
+            if self.match_ranges.iter().any(|match_range| {
+                match_range.start < line_end
+                    && line_start < match_range.end.max(match_range.start + 1)
+            }) {
+                matching_line_indexes.push(line_index);
+            }
+            line_ranges.push(line_start..line_start + line.trim_end_matches('\n').len());
+            line_start = line_end;
+        }
+        let mut last_printed_line_index_option: Option<usize> = None;
+        for (line_index, line_range) in line_ranges.iter().enumerate() {
+            let is_matching = matching_line_indexes.contains(&line_index);
+            let is_in_context = matching_line_indexes.iter().any(|&matching_line_index| {
+                matching_line_index.abs_diff(line_index) <= context_lines_count
+            });
+            if !is_in_context {
+                continue;
+            }
+            if last_printed_line_index_option
+                .is_some_and(|last_printed_line_index| last_printed_line_index + 1 < line_index)
+            {
+                rendered_matches.push_str("--\n");
+            }
+            last_printed_line_index_option = Some(line_index);
+            let separator = if is_matching { ":" } else { "-" };
+            rendered_matches.push_str(&format!(
+                "{}{} {}\n",
+                (line_index + 1).to_string().green(),
+                separator,
+                self.highlight_line(line_range)
+            ));
+        }
+        rendered_matches
+    }
+
+    fn highlight_line(&self, line_range: &Range<usize>) -> String {
+        let mut highlighted_line = String::new();
+        let mut position = line_range.start;
+        for match_range in &self.match_ranges {
+            let start = match_range.start.clamp(line_range.start, line_range.end);
+            let end = match_range.end.clamp(line_range.start, line_range.end);
+            if start >= end || start < position {
+                continue;
+            }
+            highlighted_line.push_str(&self.template[position..start]);
+            highlighted_line.push_str(&self.template[start..end].red().bold().to_string());
+            position = end;
+        }
+        highlighted_line.push_str(&self.template[position..line_range.end]);
+        highlighted_line
+    }
+}
+
+// Searches the templates of the licenses, which may match across lines, and returns only the
+// licenses with at least one match.
+pub fn search_templates<'a>(licenses: &[&'a License], regex: &Regex) -> Vec<LicenseMatches<'a>> {
+    licenses
+        .iter()
+        .map(|license| {
+            let template = license.fetch_template();
+            LicenseMatches {
+                license,
+                template,
+                match_ranges: regex
+                    .find_iter(template)
+                    .map(|found_match| found_match.range())
+                    .collect(),
+            }
+        })
+        .filter(|license_matches| !license_matches.match_ranges.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Licenses;
+
+    #[test]
+    fn search_templates_with_pattern() {
+        colored::control::set_override(false);
+        let licenses = Licenses::from_description_file();
+        let searched_licenses = vec![
+            licenses.find_license("mit").unwrap(),
+            licenses.find_license("bsd-3-clause").unwrap(),
+        ];
+        let regex = Regex::new(r"endorse\s+or\s+promote").unwrap();
+        let licenses_matches = search_templates(&searched_licenses, &regex);
+        assert_eq!(licenses_matches.len(), 1);
+        assert_eq!(licenses_matches[0].license().name(), "bsd-3-clause");
+        assert_eq!(licenses_matches[0].matches_count(), 1);
+        assert_eq!(
+            licenses_matches[0].render(1),
+            "\
+bsd-3-clause (BSD 3-Clause “New” or “Revised” License)
+15- 3. Neither the name of the copyright holder nor the names of its
+16:    contributors may be used to endorse or promote products derived from
+17-    this software without specific prior written permission.
+"
+        );
+    }
+
+    #[test]
+    fn render_license_matches_with_separated_groups() {
+        colored::control::set_override(false);
+        let licenses = Licenses::from_description_file();
+        let searched_licenses = vec![licenses.find_license("mit").unwrap()];
+        let regex = Regex::new("Software").unwrap();
+        let licenses_matches = search_templates(&searched_licenses, &regex);
+        let rendered_matches = licenses_matches[0].render(0);
+        assert!(rendered_matches.starts_with("mit (MIT License)\n6: of this software"));
+        assert!(rendered_matches.contains("\n--\n"));
+    }
+}
@@ -0,0 +1,228 @@
+const SPDX_EXPRESSION_OPERATORS: [&str; 3] = ["AND", "OR", "WITH"];
+// Families of licenses with `-only` and `-or-later` variants, lowercase.
+const GNU_LICENSE_PREFIXES: [&str; 4] = ["gpl-", "lgpl-", "agpl-", "gfdl-"];
+
+// Lowercases the identifier, and converts the deprecated identifiers of the GNU licenses, such as
+// `GPL-3.0` and `GPL-2.0+`, to their `-only` and `-or-later` forms, so that identifiers of the same
+// license compare equal.
+pub fn canonicalize_license_identifier(identifier: &str) -> String {
+    let identifier = identifier.trim().to_lowercase();
+    if let Some(base_identifier) = identifier.strip_suffix('+') {
+        return format!("{}-or-later", base_identifier);
+    }
+    let is_versioned_gnu_license = GNU_LICENSE_PREFIXES
+        .iter()
+        .any(|prefix| identifier.starts_with(prefix))
+        && identifier.ends_with(|character: char| character.is_ascii_digit());
+    if is_versioned_gnu_license {
+        return format!("{}-only", identifier);
+    }
+    identifier
+}
+
+// The normalized identifier without its `-only` or `-or-later` variant, e.g. `gpl-3.0` for
+// `GPL-3.0-or-later`.
+pub fn strip_license_variant(identifier: &str) -> String {
+    let identifier = canonicalize_license_identifier(identifier);
+    identifier
+        .strip_suffix("-only")
+        .or_else(|| identifier.strip_suffix("-or-later"))
+        .unwrap_or(&identifier)
+        .to_string()
+}
+
+// Returns the license and exception identifiers of an SPDX license expression in the order of
+// their first appearance, e.g. `MIT OR (Apache-2.0 WITH LLVM-exception)`.
+pub fn find_expression_identifiers(expression: &str) -> Vec<String> {
+    let mut identifiers: Vec<String> = Vec::new();
+    for token in expression.replace(['(', ')'], " ").split_whitespace() {
+        let is_operator = SPDX_EXPRESSION_OPERATORS
+            .iter()
+            .any(|operator| operator.eq_ignore_ascii_case(token));
+        if !is_operator && !identifiers.iter().any(|identifier| identifier == token) {
+            identifiers.push(token.to_string());
+        }
+    }
+    identifiers
+}
+
+// A parsed SPDX license expression. `WITH` binds tighter than `AND`, which binds tighter than `OR`.
+#[derive(Debug, PartialEq)]
+pub enum Expression {
+    License(String),
+    With(Box<Expression>, String),
+    And(Vec<Expression>),
+    Or(Vec<Expression>),
+}
+
+fn tokenize_expression(expression: &str) -> Vec<String> {
+    expression
+        .replace('(', " ( ")
+        .replace(')', " ) ")
+        .split_whitespace()
+        .map(|token| token.to_string())
+        .collect()
+}
+
+struct ExpressionParser {
+    tokens: Vec<String>,
+    position: usize,
+}
+
+impl ExpressionParser {
+    fn peek_operator(&self, operator: &str) -> bool {
+        self.tokens
+            .get(self.position)
+            .is_some_and(|token| token.eq_ignore_ascii_case(operator))
+    }
+
+    fn next_token(&mut self) -> Result<String, String> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or("unexpected end of the expression")?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn parse_or(&mut self) -> Result<Expression, String> {
+        let mut operands = vec![self.parse_and()?];
+        while self.peek_operator("OR") {
+            self.position += 1;
+            operands.push(self.parse_and()?);
+        }
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            Expression::Or(operands)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, String> {
+        let mut operands = vec![self.parse_with()?];
+        while self.peek_operator("AND") {
+            self.position += 1;
+            operands.push(self.parse_with()?);
+        }
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            Expression::And(operands)
+        })
+    }
+
+    fn parse_with(&mut self) -> Result<Expression, String> {
+        let operand = self.parse_operand()?;
+        if self.peek_operator("WITH") {
+            self.position += 1;
+            let exception = self.next_token()?;
+            return Ok(Expression::With(Box::new(operand), exception));
+        }
+        Ok(operand)
+    }
+
+    fn parse_operand(&mut self) -> Result<Expression, String> {
+        let token = self.next_token()?;
+        if token == "(" {
+            let expression = self.parse_or()?;
+            if self.next_token()? != ")" {
+                return Err("missing closing parenthesis".to_string());
+            }
+            return Ok(expression);
+        }
+        let is_operator = token == ")"
+            || SPDX_EXPRESSION_OPERATORS
+                .iter()
+                .any(|operator| operator.eq_ignore_ascii_case(&token));
+        if is_operator {
+            return Err(format!("unexpected '{}'", token));
+        }
+        Ok(Expression::License(token))
+    }
+}
+
+pub fn parse_expression(expression: &str) -> Result<Expression, String> {
+    let mut parser = ExpressionParser {
+        tokens: tokenize_expression(expression),
+        position: 0,
+    };
+    let parsed_expression = parser
+        .parse_or()
+        .map_err(|error| format!("invalid license expression '{}': {}", expression, error))?;
+    if parser.position < parser.tokens.len() {
+        return Err(format!(
+            "invalid license expression '{}': unexpected '{}'",
+            expression, parser.tokens[parser.position]
+        ));
+    }
+    Ok(parsed_expression)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonicalize_license_identifiers_of_gnu_licenses() {
+        assert_eq!(canonicalize_license_identifier("GPL-3.0"), "gpl-3.0-only");
+        assert_eq!(
+            canonicalize_license_identifier("GPL-2.0+"),
+            "gpl-2.0-or-later"
+        );
+        assert_eq!(
+            canonicalize_license_identifier("LGPL-2.1-or-later"),
+            "lgpl-2.1-or-later"
+        );
+        assert_eq!(canonicalize_license_identifier("Apache-2.0"), "apache-2.0");
+        assert_eq!(strip_license_variant("AGPL-3.0-or-later"), "agpl-3.0");
+        assert_eq!(strip_license_variant("MIT"), "mit");
+    }
+
+    #[test]
+    fn find_expression_identifiers_with_single_identifier() {
+        assert_eq!(find_expression_identifiers("MIT"), vec!["MIT".to_string()]);
+    }
+
+    #[test]
+    fn find_expression_identifiers_with_operators_and_parentheses() {
+        assert_eq!(
+            find_expression_identifiers("(MIT or Apache-2.0) AND (Apache-2.0 WITH LLVM-exception)"),
+            vec![
+                "MIT".to_string(),
+                "Apache-2.0".to_string(),
+                "LLVM-exception".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_expression_with_operator_precedence() {
+        let license = |identifier: &str| Expression::License(identifier.to_string());
+        assert_eq!(
+            parse_expression(
+                "MIT OR Apache-2.0 AND (BSD-3-Clause or GPL-2.0-only WITH Classpath-exception-2.0)"
+            ),
+            Ok(Expression::Or(vec![
+                license("MIT"),
+                Expression::And(vec![
+                    license("Apache-2.0"),
+                    Expression::Or(vec![
+                        license("BSD-3-Clause"),
+                        Expression::With(
+                            Box::new(license("GPL-2.0-only")),
+                            "Classpath-exception-2.0".to_string()
+                        ),
+                    ]),
+                ]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_expressions() {
+        for expression in ["", "MIT OR", "(MIT", "MIT)", "MIT Apache-2.0", "AND MIT"] {
+            assert!(parse_expression(expression).is_err());
+        }
+    }
+}
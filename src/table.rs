@@ -0,0 +1,192 @@
+use clap::ValueEnum;
+use std::env;
+use std::io;
+
+use crate::core::License;
+use crate::metadata::Copyleft;
+
+// Spaces between two columns.
+const COLUMN_GAP: usize = 4;
+const TRUNCATION_MARKER: char = '…';
+
+/// Column of the table printed by `list`
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ListColumn {
+    /// ID of the license in licensit
+    Id,
+    /// SPDX identifier
+    Spdx,
+    /// Full name
+    Name,
+    /// Approval by the Open Source Initiative
+    Osi,
+    /// Freedom according to the Free Software Foundation
+    FsfLibre,
+    /// Scope of copyleft
+    Copyleft,
+    /// Deprecation
+    Deprecated,
+}
+
+pub fn render_bool(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+impl ListColumn {
+    pub fn header(&self) -> &str {
+        match self {
+            ListColumn::Id => "ID",
+            ListColumn::Spdx => "SPDX",
+            ListColumn::Name => "NAME",
+            ListColumn::Osi => "OSI",
+            ListColumn::FsfLibre => "FSF LIBRE",
+            ListColumn::Copyleft => "COPYLEFT",
+            ListColumn::Deprecated => "DEPRECATED",
+        }
+    }
+
+    pub fn render(&self, license: &License) -> String {
+        match self {
+            ListColumn::Id => license.name().to_string(),
+            ListColumn::Spdx => license.spdx_id().to_string(),
+            ListColumn::Name => license.full_name().to_string(),
+            ListColumn::Osi => render_bool(license.osi_approved()),
+            ListColumn::FsfLibre => render_bool(license.fsf_libre()),
+            ListColumn::Copyleft => match license.copyleft() {
+                Copyleft::Strong => "strong",
+                Copyleft::Weak => "weak",
+                Copyleft::None => "none",
+            }
+            .to_string(),
+            ListColumn::Deprecated => render_bool(license.deprecated()),
+        }
+    }
+}
+
+/// Order of the licenses printed by `list`
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ListSort {
+    /// By ID
+    #[default]
+    Id,
+    /// By SPDX identifier, ignoring case
+    Spdx,
+    /// By full name, ignoring case
+    Name,
+    /// From permissive to strong copyleft licenses, then by ID
+    Copyleft,
+}
+
+impl ListSort {
+    pub fn sort(&self, licenses: &mut [&License]) {
+        match self {
+            ListSort::Id => licenses.sort_by_key(|license| license.name().to_string()),
+            ListSort::Spdx => licenses.sort_by_key(|license| license.spdx_id().to_lowercase()),
+            ListSort::Name => licenses.sort_by_key(|license| license.full_name().to_lowercase()),
+            ListSort::Copyleft => licenses.sort_by_key(|license| {
+                let copyleft_rank = match license.copyleft() {
+                    Copyleft::None => 0,
+                    Copyleft::Weak => 1,
+                    Copyleft::Strong => 2,
+                };
+                (copyleft_rank, license.name().to_string())
+            }),
+        }
+    }
+}
+
+// Width available for the table, `None` if the output is not a terminal, so that piped output is
+// never truncated. Otherwise, `COLUMNS` takes precedence over the width of the terminal.
+pub fn fetch_terminal_width() -> Option<usize> {
+    let (width, _) = terminal_size::terminal_size_of(io::stdout())?;
+    Some(
+        env::var("COLUMNS")
+            .ok()
+            .and_then(|columns| columns.parse().ok())
+            .unwrap_or(width.0 as usize),
+    )
+}
+
+fn truncate_line(line: &str, max_width: usize) -> String {
+    if line.chars().count() <= max_width {
+        return line.to_string();
+    }
+    let mut truncated_line: String = line.chars().take(max_width.saturating_sub(1)).collect();
+    truncated_line.push(TRUNCATION_MARKER);
+    truncated_line
+}
+
+// Each column is as wide as its longest cell, the last one is not padded. Lines longer than
+// `max_width` are truncated, which in practice shortens the last column.
+pub fn render_table(rows: &[Vec<String>], max_width: Option<usize>) -> String {
+    let columns_count = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let column_widths: Vec<usize> = (0..columns_count)
+        .map(|column_index| {
+            rows.iter()
+                .filter_map(|row| row.get(column_index))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let mut rendered_table = String::new();
+    for row in rows {
+        let mut line = String::new();
+        for (column_index, cell) in row.iter().enumerate() {
+            if column_index + 1 == row.len() {
+                line.push_str(cell);
+            } else {
+                let width = column_widths[column_index] + COLUMN_GAP;
+                line.push_str(&format!("{: <width$}", cell));
+            }
+        }
+        let line = line.trim_end();
+        match max_width {
+            Some(max_width) => rendered_table.push_str(&truncate_line(line, max_width)),
+            None => rendered_table.push_str(line),
+        }
+        rendered_table.push('\n');
+    }
+    rendered_table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_rows() -> Vec<Vec<String>> {
+        vec![
+            vec![
+                "gpl-3.0".to_string(),
+                "GNU General Public License v3.0".to_string(),
+            ],
+            vec![
+                "MPL-2.0-no-copyleft-exception".to_string(),
+                "Mozilla Public License 2.0 (no copyleft exception)".to_string(),
+            ],
+        ]
+    }
+
+    #[test]
+    fn render_table_with_long_ids() {
+        assert_eq!(
+            render_table(&create_rows(), None),
+            "\
+gpl-3.0                          GNU General Public License v3.0
+MPL-2.0-no-copyleft-exception    Mozilla Public License 2.0 (no copyleft exception)
+"
+        );
+    }
+
+    #[test]
+    fn render_table_with_max_width() {
+        assert_eq!(
+            render_table(&create_rows(), Some(48)),
+            "\
+gpl-3.0                          GNU General Pu…
+MPL-2.0-no-copyleft-exception    Mozilla Public…
+"
+        );
+        assert_eq!(render_table(&create_rows(), Some(4)), "gpl…\nMPL…\n");
+    }
+}
@@ -0,0 +1,12 @@
+use std::fs;
+use std::path::Path;
+
+// Writes the files, given as paths relative to `root` with their content, creating the
+// directories on the way.
+pub fn create_project_files(root: &Path, files: &[(&str, &str)]) {
+    for (relative_path, content) in files {
+        let path = root.join(relative_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
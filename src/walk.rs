@@ -0,0 +1,151 @@
+use ignore::overrides::OverrideBuilder;
+use ignore::{WalkBuilder, WalkState};
+use std::error::Error;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub const LICENSIT_IGNORE_FILE_BASENAME: &str = ".licensitignore";
+
+// Files are skipped when they match `.gitignore` (even outside of a Git repository),
+// `.licensitignore` (same syntax) or one of the `--exclude` globs.
+fn build_walker(root: &Path, exclude_globs: &[String]) -> Result<WalkBuilder, Box<dyn Error>> {
+    let mut overrides_builder = OverrideBuilder::new(root);
+    for exclude_glob in exclude_globs {
+        // Without the leading `!` an override glob whitelists files instead of excluding them.
+        overrides_builder.add(&format!("!{}", exclude_glob))?;
+    }
+    let overrides = overrides_builder.build()?;
+    let mut walker_builder = WalkBuilder::new(root);
+    walker_builder
+        .require_git(false)
+        .add_custom_ignore_filename(LICENSIT_IGNORE_FILE_BASENAME)
+        .overrides(overrides);
+    Ok(walker_builder)
+}
+
+// Visits the files on the walker's thread pool, so `visit_file` can also do the reading.
+// The walk stops at the first error returned by the walker or by `visit_file`.
+pub fn visit_files_parallel<F>(
+    root: &Path,
+    exclude_globs: &[String],
+    visit_file: F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(PathBuf) -> io::Result<()> + Sync,
+{
+    let first_error: Mutex<Option<Box<dyn Error + Send + Sync>>> = Mutex::new(None);
+    build_walker(root, exclude_globs)?.build_parallel().run(|| {
+        Box::new(|entry_result| {
+            let visit_result: Result<(), Box<dyn Error + Send + Sync>> = match entry_result {
+                Ok(entry)
+                    if entry
+                        .file_type()
+                        .is_some_and(|file_type| file_type.is_file()) =>
+                {
+                    visit_file(entry.into_path()).map_err(|error| error.into())
+                }
+                Ok(_) => Ok(()),
+                Err(error) => Err(error.into()),
+            };
+            if let Err(error) = visit_result {
+                first_error.lock().unwrap().get_or_insert(error);
+                return WalkState::Quit;
+            }
+            WalkState::Continue
+        })
+    });
+    if let Some(error) = first_error.into_inner().unwrap() {
+        return Err(error);
+    }
+    Ok(())
+}
+
+pub fn display_relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_project_files;
+
+    fn walk_relative_files(root: &Path, exclude_globs: &[String]) -> Vec<String> {
+        let relative_files = Mutex::new(Vec::new());
+        visit_files_parallel(root, exclude_globs, |path| {
+            let relative_file = display_relative_path(root, &path);
+            relative_files.lock().unwrap().push(relative_file);
+            Ok(())
+        })
+        .unwrap();
+        let mut relative_files = relative_files.into_inner().unwrap();
+        relative_files.sort();
+        relative_files
+    }
+
+    #[test]
+    fn walk_files_honors_gitignore_outside_of_git_repository() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                ("src/main.rs", "content"),
+                ("target/debug/build.rs", "content"),
+                ("node_modules/a/index.js", "content"),
+                (".gitignore", "target/\nnode_modules/\n"),
+            ],
+        );
+        assert_eq!(
+            walk_relative_files(temp_dir.path(), &[]),
+            vec!["src/main.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn walk_files_honors_licensitignore() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                ("src/main.rs", "content"),
+                ("vendor/lib/lib.rs", "content"),
+                (LICENSIT_IGNORE_FILE_BASENAME, "vendor/\n"),
+            ],
+        );
+        assert_eq!(
+            walk_relative_files(temp_dir.path(), &[]),
+            vec!["src/main.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn walk_files_honors_exclude_globs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(
+            temp_dir.path(),
+            &[
+                ("src/main.rs", "content"),
+                ("src/generated.pb.rs", "content"),
+                ("third_party/lib.c", "content"),
+            ],
+        );
+        let exclude_globs = vec!["*.pb.rs".to_string(), "third_party/".to_string()];
+        assert_eq!(
+            walk_relative_files(temp_dir.path(), &exclude_globs),
+            vec!["src/main.rs".to_string()]
+        );
+    }
+
+    #[test]
+    fn visit_files_parallel_returns_visitor_error() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_project_files(temp_dir.path(), &[("src/main.rs", "content")]);
+        let visit_result = visit_files_parallel(temp_dir.path(), &[], |_| {
+            Err(io::Error::other("visitor error"))
+        });
+        assert_eq!(visit_result.unwrap_err().to_string(), "visitor error");
+    }
+}
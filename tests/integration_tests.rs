@@ -34,12 +34,19 @@ SOFTWARE.
 ";
 const MIT_LICENSE_NAME: &str = "mit";
 
+// The commands that scan files cache their results there rather than in the user's cache.
+fn find_test_cache_dir() -> PathBuf {
+    std::env::temp_dir().join("licensit-tests-cache")
+}
+
 pub struct TempDirContext {
     original_dir: PathBuf,
     temp_dir: TempDir,
 }
 
 impl TempDirContext {
+    // Not `Default`, as it changes the current directory.
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         let original_dir = std::env::current_dir().unwrap();
         let temp_dir = tempfile::tempdir().unwrap();
@@ -81,7 +88,7 @@ fn render_mit_license_with_fillers(
 
 fn create_licensit_list_command() -> Command {
     let mut licensit_list_command = Command::cargo_bin("licensit").unwrap();
-    licensit_list_command.arg("list");
+    licensit_list_command.arg("list").env_remove("COLUMNS");
     licensit_list_command
 }
 
@@ -91,12 +98,92 @@ fn create_licensit_show_command() -> Command {
     licensit_show_command
 }
 
+fn create_licensit_info_command() -> Command {
+    let mut licensit_info_command = Command::cargo_bin("licensit").unwrap();
+    licensit_info_command.arg("info");
+    licensit_info_command
+}
+
 fn create_licensit_add_command() -> Command {
     let mut licensit_add_command = Command::cargo_bin("licensit").unwrap();
     licensit_add_command.arg("add");
     licensit_add_command
 }
 
+fn create_licensit_sync_manifest_command() -> Command {
+    let mut licensit_sync_manifest_command = Command::cargo_bin("licensit").unwrap();
+    licensit_sync_manifest_command.arg("sync-manifest");
+    licensit_sync_manifest_command
+}
+
+fn create_licensit_consistency_command() -> Command {
+    let mut licensit_consistency_command = Command::cargo_bin("licensit").unwrap();
+    licensit_consistency_command.arg("consistency");
+    licensit_consistency_command
+}
+
+fn create_licensit_debian_copyright_command() -> Command {
+    let mut licensit_debian_copyright_command = Command::cargo_bin("licensit").unwrap();
+    licensit_debian_copyright_command
+        .arg("debian-copyright")
+        .env("XDG_CACHE_HOME", find_test_cache_dir());
+    licensit_debian_copyright_command
+}
+
+fn create_licensit_deps_command() -> Command {
+    let mut licensit_deps_command = Command::cargo_bin("licensit").unwrap();
+    licensit_deps_command.arg("deps");
+    licensit_deps_command
+}
+
+fn create_licensit_notices_command() -> Command {
+    let mut licensit_notices_command = Command::cargo_bin("licensit").unwrap();
+    licensit_notices_command.arg("notices");
+    licensit_notices_command
+}
+
+fn create_licensit_policy_command() -> Command {
+    let mut licensit_policy_command = Command::cargo_bin("licensit").unwrap();
+    licensit_policy_command.arg("policy");
+    licensit_policy_command
+}
+
+fn create_licensit_compatibility_command() -> Command {
+    let mut licensit_compatibility_command = Command::cargo_bin("licensit").unwrap();
+    licensit_compatibility_command.arg("compatibility");
+    licensit_compatibility_command
+}
+
+fn create_licensit_sbom_command() -> Command {
+    let mut licensit_sbom_command = Command::cargo_bin("licensit").unwrap();
+    licensit_sbom_command.arg("sbom");
+    licensit_sbom_command
+}
+
+fn create_licensit_headers_command() -> Command {
+    let mut licensit_headers_command = Command::cargo_bin("licensit").unwrap();
+    licensit_headers_command
+        .arg("headers")
+        .env("XDG_CACHE_HOME", find_test_cache_dir());
+    licensit_headers_command
+}
+
+fn create_licensit_reuse_command() -> Command {
+    let mut licensit_reuse_command = Command::cargo_bin("licensit").unwrap();
+    licensit_reuse_command
+        .arg("reuse")
+        .env("XDG_CACHE_HOME", find_test_cache_dir());
+    licensit_reuse_command
+}
+
+fn create_project_files(root: &Path, files: &[(&str, &str)]) {
+    for (relative_path, content) in files {
+        let path = root.join(relative_path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
 #[test]
 fn licensit_list() {
     create_licensit_list_command().assert().success().stdout(
@@ -118,6 +205,90 @@ unlicense       The Unlicense
     );
 }
 
+#[test]
+fn licensit_list_with_filters() {
+    create_licensit_list_command()
+        .args(["--osi", "--copyleft", "weak"])
+        .assert()
+        .success()
+        .stdout(
+            "\
+epl-2.0     Eclipse Public License 2.0
+lgpl-2.1    GNU Lesser General Public License v2.1
+mpl-2.0     Mozilla Public License 2.0
+",
+        );
+}
+
+#[test]
+fn licensit_list_with_deprecated_option() {
+    create_licensit_list_command()
+        .arg("--deprecated")
+        .assert()
+        .success()
+        .stdout(
+            "\
+agpl-3.0    GNU Affero General Public License v3.0
+gpl-2.0     GNU General Public License v2.0
+gpl-3.0     GNU General Public License v3.0
+lgpl-2.1    GNU Lesser General Public License v2.1
+",
+        );
+    create_licensit_list_command()
+        .args(["--deprecated", "--permissive"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn licensit_list_with_search_option() {
+    create_licensit_list_command()
+        .args(["--permissive", "--search", "BSD"])
+        .assert()
+        .success()
+        .stdout(
+            "\
+bsd-2-clause    BSD 2-Clause “Simplified” License
+bsd-3-clause    BSD 3-Clause “New” or “Revised” License
+",
+        );
+}
+
+#[test]
+fn licensit_list_with_columns_and_sort_options() {
+    create_licensit_list_command()
+        .args([
+            "--columns",
+            "spdx,copyleft,osi",
+            "--sort",
+            "copyleft",
+            "--reverse",
+        ])
+        .args(["--search", "gpl"])
+        .assert()
+        .success()
+        .stdout(
+            "\
+SPDX             COPYLEFT    OSI
+GPL-3.0-only     strong      yes
+GPL-2.0-only     strong      yes
+AGPL-3.0-only    strong      yes
+LGPL-2.1-only    weak        yes
+",
+        );
+}
+
+#[test]
+fn licensit_list_with_columns_environment_variable_and_piped_output() {
+    create_licensit_list_command()
+        .env("COLUMNS", "24")
+        .args(["--search", "apache"])
+        .assert()
+        .success()
+        .stdout("apache-2.0    Apache License 2.0\n");
+}
+
 #[test]
 fn licensit_show_with_user_option() {
     let user_option_with_value = format!("--user={}", USER_OPTION_VALUE);
@@ -289,6 +460,222 @@ For more information, try '--help'.
         );
 }
 
+#[test]
+fn licensit_show_with_ids_option() {
+    create_licensit_show_command()
+        .arg("apache-2.0")
+        .arg("--ids")
+        .assert()
+        .success()
+        .stdout(
+            "\
+SPDX            Apache-2.0
+PyPI            License :: OSI Approved :: Apache Software License
+Debian          Apache-2.0
+Fedora          ASL 2.0
+Maven           Apache License, Version 2.0
+Maven URL       https://www.apache.org/licenses/LICENSE-2.0.txt
+",
+        );
+}
+
+#[test]
+fn licensit_show_with_summary_option() {
+    create_licensit_show_command()
+        .arg(MIT_LICENSE_NAME)
+        .arg("--summary")
+        .assert()
+        .success()
+        .stdout(
+            "\
+MIT License
+
+You can use, modify and distribute the software for any purpose, including in
+proprietary products, as long as you keep the copyright notice and the license.
+The software comes without warranty.
+
+Not legal advice, see `licensit show mit` for the license text.
+",
+        );
+}
+
+#[test]
+fn licensit_info() {
+    create_licensit_info_command()
+        .arg(MIT_LICENSE_NAME)
+        .assert()
+        .success()
+        .stdout("Name            mit\nSPDX            MIT\nFull name       MIT License\nOSI approved    yes\nFSF libre       yes\n\nPermissions\n    Commercial use\n    Modification\n    Distribution\n    Private use\n\nConditions\n    License and copyright notice\n\nLimitations\n    Liability\n    Warranty\n");
+}
+
+#[test]
+fn licensit_info_with_nonexistent_license() {
+    create_licensit_info_command()
+        .arg("nonexistent")
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn licensit_compare() {
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["compare", "mit", "apache-2.0", "mpl-2.0"])
+        .assert()
+        .success()
+        .stdout(
+            "                           mit     apache-2.0    mpl-2.0
+SPDX                       MIT     Apache-2.0    MPL-2.0
+Copyleft                   none    none          weak
+Permissions
+    Patent use             no      yes           yes
+Conditions
+    State changes          no      yes           no
+    Disclose source        no      no            yes
+    Same license (file)    no      no            yes
+Limitations
+    Trademark use          no      yes           yes
+",
+        );
+}
+
+#[test]
+fn licensit_compare_with_nonexistent_license() {
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["compare", "mit", "nonexistent"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn licensit_diff() {
+    let output = Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["diff", "bsd-2-clause", "bsd-3-clause"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rendered_diff = String::from_utf8(output.stdout).unwrap();
+    assert!(rendered_diff.starts_with("BSD [-2-Clause-]{+3-Clause+} License\n"));
+    assert!(rendered_diff.contains("{+3. Neither the name of the copyright holder"));
+    assert!(!rendered_diff.contains("[-Redistributions"));
+}
+
+#[test]
+fn licensit_search() {
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args([
+            "search",
+            "--ignore-case",
+            "ENDORSE",
+            "bsd-2-clause",
+            "bsd-3-clause",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            "\
+bsd-3-clause (BSD 3-Clause “New” or “Revised” License)
+15- 3. Neither the name of the copyright holder nor the names of its
+16:    contributors may be used to endorse or promote products derived from
+17-    this software without specific prior written permission.
+",
+        );
+}
+
+#[test]
+fn licensit_search_with_list_option() {
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args([
+            "search",
+            "--list",
+            "trademark",
+            "mit",
+            "apache-2.0",
+            "cc0-1.0",
+        ])
+        .assert()
+        .success()
+        .stdout("apache-2.0      2 matches\ncc0-1.0         1 match\n");
+}
+
+#[test]
+fn licensit_search_without_match() {
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["search", "trademark", "mit"])
+        .assert()
+        .code(1)
+        .stdout("");
+}
+
+#[test]
+fn licensit_info_with_json_format() {
+    let output = Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["--format", "json", "info", MIT_LICENSE_NAME])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let description: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(description["spdx_id"], "MIT");
+    assert_eq!(description["copyleft"], "none");
+    assert_eq!(description["osi_approved"], true);
+    assert_eq!(description["conditions"][0], "include-copyright");
+    assert_eq!(description["ids"]["debian"], "Expat");
+}
+
+#[test]
+fn licensit_list_with_yaml_format() {
+    let output = Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["list", "--format", "yaml", "--search", "boost"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let descriptions: serde_yaml::Value = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!(descriptions.as_sequence().unwrap().len(), 1);
+    assert_eq!(descriptions[0]["name"], "bsl-1.0");
+    assert_eq!(descriptions[0]["full_name"], "Boost Software License 1.0");
+}
+
+#[test]
+fn licensit_show_with_json_format() {
+    let output = Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["--format", "json", "show", MIT_LICENSE_NAME])
+        .args(["--user", USER_OPTION_VALUE, "--year", YEAR_OPTION_VALUE])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rendered_license: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        rendered_license["text"],
+        render_mit_license_with_fillers(Some(USER_OPTION_VALUE), Some(YEAR_OPTION_VALUE))
+    );
+    assert_eq!(
+        rendered_license["placeholders"][0],
+        serde_json::json!({
+            "field": "author",
+            "placeholder": "[fullname]",
+            "value": USER_OPTION_VALUE,
+        })
+    );
+}
+
+#[test]
+fn licensit_format_with_unsupported_command() {
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args(["headers", "--format", "json"])
+        .assert()
+        .failure()
+        .stdout("--format is only supported by list, show and info\n");
+}
+
 #[test]
 #[serial]
 fn licensit_add_with_user_option() {
@@ -349,6 +736,98 @@ fn licensit_add_with_user_option_and_env_variable() {
     );
 }
 
+#[test]
+#[serial]
+fn licensit_add_with_project_config() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[(
+            ".licensit.toml",
+            "license = \"mit\"\nholders = [\"Jane Doe\", \"Example Corp\"]\nyear = 2023\noutput = \"LICENSE.txt\"\n",
+        )],
+    );
+    let nested_dir = temp_dir_context.path().join("src");
+    fs::create_dir(&nested_dir).unwrap();
+    create_licensit_add_command()
+        .current_dir(&nested_dir)
+        .assert()
+        .success();
+    let added_license_file_content = fs::read_to_string(nested_dir.join("LICENSE.txt")).unwrap();
+    assert_eq!(
+        added_license_file_content,
+        render_mit_license_with_fillers(Some("Jane Doe, Example Corp"), Some(YEAR_OPTION_VALUE))
+    );
+    assert!(!temp_dir_context.path().join("LICENSE.txt").exists());
+    create_licensit_add_command()
+        .args(["--user", USER_OPTION_VALUE])
+        .assert()
+        .success();
+    let added_license_file_content =
+        fs::read_to_string(temp_dir_context.path().join("LICENSE.txt")).unwrap();
+    assert_eq!(
+        added_license_file_content,
+        render_mit_license_with_fillers(Some(USER_OPTION_VALUE), Some(YEAR_OPTION_VALUE))
+    );
+}
+
+#[test]
+#[serial]
+fn licensit_add_without_license() {
+    let _temp_dir_context = TempDirContext::new();
+    create_licensit_add_command()
+        .assert()
+        .failure()
+        .stdout("no license given, and no license set in .licensit.toml\n");
+}
+
+#[test]
+#[serial]
+fn licensit_choose() {
+    let temp_dir_context = TempDirContext::new();
+    Command::cargo_bin("licensit")
+        .unwrap()
+        .args([
+            "choose",
+            "--user",
+            USER_OPTION_VALUE,
+            "--year",
+            YEAR_OPTION_VALUE,
+        ])
+        .write_stdin("p\nn\nmpl-2.0\nmit\n")
+        .assert()
+        .success()
+        .stdout(
+            "\
+Under which license can modified versions be distributed?
+  p) Any license, even proprietary (permissive) [7]
+  w) The same license for the modified files or library only (weak copyleft) [3]
+  s) The same license for the whole work (strong copyleft) [3]
+Answer (Enter for no preference): 
+Should contributors explicitly grant their patent rights to users?
+  y) Yes [1]
+  n) No [6]
+Answer (Enter for no preference): 
+Matching licenses:
+  bsd-2-clause    BSD 2-Clause “Simplified” License
+  bsd-3-clause    BSD 3-Clause “New” or “Revised” License
+  bsl-1.0         Boost Software License 1.0
+  cc0-1.0         Creative Commons Zero v1.0 Universal
+  mit             MIT License
+  unlicense       The Unlicense
+License to add to the current directory (Enter to quit): \
+Please enter one of the matching licenses
+License to add to the current directory (Enter to quit): Added MIT License to LICENSE
+",
+        );
+    let added_license_file_content =
+        fs::read_to_string(temp_dir_context.path().join("LICENSE")).unwrap();
+    assert_eq!(
+        added_license_file_content,
+        render_mit_license_with_fillers(Some(USER_OPTION_VALUE), Some(YEAR_OPTION_VALUE))
+    );
+}
+
 #[test]
 #[serial]
 fn licensit_add_with_user_and_year_options() {
@@ -417,3 +896,583 @@ fn licensit_add_with_all_options_and_env_variable() {
         render_mit_license_with_fillers(Some(USER_OPTION_VALUE), Some(YEAR_OPTION_VALUE))
     );
 }
+
+#[test]
+fn licensit_headers_with_ignore_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_project_files(
+        temp_dir.path(),
+        &[
+            (".gitignore", "target/\n"),
+            (".licensitignore", "vendor/\n"),
+            (
+                "src/main.rs",
+                "// SPDX-License-Identifier: MIT\nfn main() {}\n",
+            ),
+            ("src/lib.rs", "pub fn lib() {}\n"),
+            ("target/debug/build.rs", "fn main() {}\n"),
+            ("vendor/dependency/lib.rs", "pub fn dependency() {}\n"),
+        ],
+    );
+    create_licensit_headers_command()
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout("src/lib.rs\n");
+}
+
+#[test]
+fn licensit_headers_with_project_config_in_ancestor_directory() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_project_files(
+        temp_dir.path(),
+        &[
+            (".licensit.toml", "exclude = [\"src/gen/**\"]\n"),
+            ("src/lib.rs", "pub fn lib() {}\n"),
+            ("src/gen/schema.rs", "pub struct Schema;\n"),
+        ],
+    );
+    create_licensit_headers_command()
+        .arg(temp_dir.path().join("src"))
+        .assert()
+        .success()
+        .stdout("lib.rs\n");
+    create_licensit_headers_command()
+        .current_dir(temp_dir.path().join("src"))
+        .assert()
+        .success()
+        .stdout("lib.rs\n");
+}
+
+#[test]
+fn licensit_headers_with_project_config() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_project_files(
+        temp_dir.path(),
+        &[
+            (
+                ".licensit.toml",
+                "header-style = \"license-and-copyright\"\nexclude = [\"vendor/\"]\n",
+            ),
+            (
+                "src/main.rs",
+                "// SPDX-FileCopyrightText: 2024 Jane Doe\n// SPDX-License-Identifier: MIT\nfn main() {}\n",
+            ),
+            (
+                "src/lib.rs",
+                "// SPDX-License-Identifier: MIT\npub fn lib() {}\n",
+            ),
+            ("vendor/dependency/lib.rs", "pub fn dependency() {}\n"),
+        ],
+    );
+    create_licensit_headers_command()
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout("src/lib.rs\n");
+    create_licensit_headers_command()
+        .arg(temp_dir.path())
+        .args(["--style", "license"])
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn licensit_headers_with_sidecar_files() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_project_files(
+        temp_dir.path(),
+        &[
+            ("assets/logo.png", "\u{89}PNG\r\n"),
+            (
+                "assets/logo.png.license",
+                "SPDX-FileCopyrightText: 2024 Jane Doe\nSPDX-License-Identifier: CC0-1.0\n",
+            ),
+            ("assets/icon.png", "\u{89}PNG\r\n"),
+        ],
+    );
+    create_licensit_headers_command()
+        .arg(temp_dir.path())
+        .args(["--style", "license-and-copyright"])
+        .assert()
+        .success()
+        .stdout("assets/icon.png\n");
+}
+
+#[test]
+fn licensit_headers_with_exclude_and_check_options() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_project_files(
+        temp_dir.path(),
+        &[
+            ("src/lib.rs", "pub fn lib() {}\n"),
+            ("src/generated.rs", "pub fn generated() {}\n"),
+        ],
+    );
+    create_licensit_headers_command()
+        .arg(temp_dir.path())
+        .arg("--exclude=*generated.rs")
+        .arg("--check")
+        .assert()
+        .failure()
+        .stdout("src/lib.rs\n");
+    create_licensit_headers_command()
+        .arg(temp_dir.path())
+        .arg("--exclude=src/")
+        .arg("--check")
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+#[serial]
+fn licensit_reuse_init_with_licenses() {
+    let temp_dir_context = TempDirContext::new();
+    create_licensit_reuse_command()
+        .arg("init")
+        .arg(MIT_LICENSE_NAME)
+        .arg("apache-2.0")
+        .assert()
+        .success()
+        .stdout("Created LICENSES/MIT.txt\nCreated LICENSES/Apache-2.0.txt\n");
+    let mit_license_text =
+        fs::read_to_string(temp_dir_context.path().join("LICENSES/MIT.txt")).unwrap();
+    assert_eq!(mit_license_text, MIT_LICENSE);
+}
+
+#[test]
+#[serial]
+fn licensit_reuse_init_with_referenced_licenses() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            ("LICENSES/MIT.txt", MIT_LICENSE),
+            (
+                "src/main.rs",
+                "// SPDX-License-Identifier: MIT OR GPL-3.0-or-later OR LicenseRef-Custom\n",
+            ),
+        ],
+    );
+    create_licensit_reuse_command()
+        .arg("init")
+        .assert()
+        .success()
+        .stdout("Created LICENSES/GPL-3.0-or-later.txt\n");
+    assert!(temp_dir_context
+        .path()
+        .join("LICENSES/GPL-3.0-or-later.txt")
+        .exists());
+}
+
+#[test]
+fn licensit_reuse_lint() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    create_project_files(
+        temp_dir.path(),
+        &[
+            ("LICENSES/MIT.txt", MIT_LICENSE),
+            (
+                "src/main.rs",
+                "// SPDX-FileCopyrightText: 2024 Jane Doe\n// SPDX-License-Identifier: MIT\n",
+            ),
+        ],
+    );
+    create_licensit_reuse_command()
+        .arg("lint")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout("The project is compliant with the REUSE specification\n");
+    create_project_files(temp_dir.path(), &[("src/lib.rs", "pub fn lib() {}\n")]);
+    create_licensit_reuse_command()
+        .arg("lint")
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stdout(
+            "\
+Files without copyright information:
+    src/lib.rs
+Files without license information:
+    src/lib.rs
+",
+        );
+}
+
+#[test]
+#[serial]
+fn licensit_reuse_annotate_covers_files_without_header() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            ("LICENSES/CC0-1.0.txt", "CC0 1.0 Universal"),
+            ("assets/logo.png", "binary"),
+        ],
+    );
+    create_licensit_headers_command()
+        .arg("--check")
+        .assert()
+        .failure()
+        .stdout("assets/logo.png\n");
+    create_licensit_reuse_command()
+        .arg("annotate")
+        .arg("assets/*.png")
+        .arg("--license=CC0-1.0")
+        .arg("--copyright=2024 Jane Doe")
+        .assert()
+        .success();
+    create_licensit_headers_command()
+        .arg("--check")
+        .assert()
+        .success()
+        .stdout("");
+    create_licensit_reuse_command()
+        .arg("lint")
+        .assert()
+        .success()
+        .stdout("The project is compliant with the REUSE specification\n");
+}
+
+#[test]
+#[serial]
+fn licensit_add_with_sync_manifest_option() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[("Cargo.toml", "[package]\nname = \"crate\"\n")],
+    );
+    let user_option_with_value = format!("--user={}", USER_OPTION_VALUE);
+    create_licensit_add_command()
+        .arg(MIT_LICENSE_NAME)
+        .arg(user_option_with_value)
+        .arg("--sync-manifest")
+        .assert()
+        .success()
+        .stdout("Updated Cargo.toml\n");
+    let manifest_content = fs::read_to_string(temp_dir_context.path().join("Cargo.toml")).unwrap();
+    assert_eq!(
+        manifest_content,
+        "[package]\nname = \"crate\"\nlicense = \"MIT\"\n"
+    );
+}
+
+#[test]
+#[serial]
+fn licensit_sync_manifest() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[(
+            "Cargo.toml",
+            "[package]\nname = \"crate\"\nlicense = \"MIT\"\n",
+        )],
+    );
+    create_licensit_sync_manifest_command()
+        .arg("apache-2.0")
+        .assert()
+        .success();
+    let manifest_content = fs::read_to_string(temp_dir_context.path().join("Cargo.toml")).unwrap();
+    assert_eq!(
+        manifest_content,
+        "[package]\nname = \"crate\"\nlicense = \"Apache-2.0\"\n"
+    );
+    create_licensit_sync_manifest_command()
+        .arg("--license-file=LICENSE.custom")
+        .assert()
+        .success();
+    let manifest_content = fs::read_to_string(temp_dir_context.path().join("Cargo.toml")).unwrap();
+    assert_eq!(
+        manifest_content,
+        "[package]\nname = \"crate\"\nlicense-file = \"LICENSE.custom\"\n"
+    );
+}
+
+#[test]
+#[serial]
+fn licensit_sync_manifest_with_several_manifests() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            ("package.json", "{\n  \"name\": \"package\"\n}\n"),
+            (
+                "pyproject.toml",
+                "[project]\nname = \"package\"\nlicense = \"MIT\"\n",
+            ),
+        ],
+    );
+    create_licensit_sync_manifest_command()
+        .arg("apache-2.0")
+        .assert()
+        .success()
+        .stdout("Updated package.json\nUpdated pyproject.toml\n");
+    let npm_manifest_content =
+        fs::read_to_string(temp_dir_context.path().join("package.json")).unwrap();
+    assert_eq!(
+        npm_manifest_content,
+        "{\n  \"name\": \"package\",\n  \"license\": \"Apache-2.0\"\n}\n"
+    );
+    let pyproject_manifest_content =
+        fs::read_to_string(temp_dir_context.path().join("pyproject.toml")).unwrap();
+    assert_eq!(
+        pyproject_manifest_content,
+        "[project]\nname = \"package\"\nlicense = \"Apache-2.0\"\n"
+    );
+}
+
+#[test]
+#[serial]
+fn licensit_sync_manifest_without_manifest() {
+    let _temp_dir_context = TempDirContext::new();
+    create_licensit_sync_manifest_command()
+        .arg("mit")
+        .assert()
+        .failure();
+}
+
+#[test]
+#[serial]
+fn licensit_consistency() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[(
+            "Cargo.toml",
+            "[package]\nname = \"crate\"\nlicense = \"MIT\"\n",
+        )],
+    );
+    let user_option_with_value = format!("--user={}", USER_OPTION_VALUE);
+    create_licensit_add_command()
+        .arg(MIT_LICENSE_NAME)
+        .arg(&user_option_with_value)
+        .assert()
+        .success();
+    create_licensit_consistency_command()
+        .assert()
+        .success()
+        .stdout("License files:\n    LICENSE: MIT\nPackage manifests:\n    Cargo.toml: MIT\n");
+    create_licensit_add_command()
+        .arg("apache-2.0")
+        .arg(&user_option_with_value)
+        .assert()
+        .success();
+    create_licensit_consistency_command()
+        .assert()
+        .failure()
+        .stdout(
+            "License files:\n    LICENSE: Apache-2.0\nPackage manifests:\n    Cargo.toml: MIT (disagrees)\nThe license files and the package manifests are inconsistent\n",
+        );
+}
+
+#[test]
+#[serial]
+fn licensit_debian_copyright() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[("src/main.rs", "fn main() {}\n")],
+    );
+    let user_option_with_value = format!("--user={}", USER_OPTION_VALUE);
+    let year_option_with_value = format!("--year={}", YEAR_OPTION_VALUE);
+    create_licensit_add_command()
+        .arg(MIT_LICENSE_NAME)
+        .arg(&user_option_with_value)
+        .assert()
+        .success();
+    create_licensit_debian_copyright_command()
+        .arg(&user_option_with_value)
+        .arg(&year_option_with_value)
+        .assert()
+        .success()
+        .stdout("Created debian/copyright\n");
+    let debian_copyright =
+        fs::read_to_string(temp_dir_context.path().join("debian/copyright")).unwrap();
+    let expected_files_paragraph = format!(
+        "Files: *\nCopyright: {} {}\nLicense: Expat\n\nLicense: Expat\n MIT License\n",
+        YEAR_OPTION_VALUE, USER_OPTION_VALUE
+    );
+    assert!(debian_copyright.starts_with(
+        "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n"
+    ));
+    assert!(debian_copyright.contains(&expected_files_paragraph));
+}
+
+#[test]
+#[serial]
+fn licensit_deps() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"either\"\nversion = \"1.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            (
+                "vendor/either/Cargo.toml",
+                "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+            ),
+            ("vendor/either/LICENSE-MIT", "MIT License"),
+        ],
+    );
+    create_licensit_deps_command()
+        .env("CARGO_HOME", temp_dir_context.path().join("cargo"))
+        .assert()
+        .success()
+        .stdout("MIT OR Apache-2.0 (1)\n    either                          1.9.0           LICENSE-MIT\n");
+}
+
+#[test]
+#[serial]
+fn licensit_deps_with_node_modules() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            (
+                "node_modules/left-pad/package.json",
+                "{\"name\": \"left-pad\", \"version\": \"1.3.0\", \"license\": \"WTFPL\"}",
+            ),
+            ("node_modules/left-pad/LICENSE", "WTFPL"),
+        ],
+    );
+    create_licensit_deps_command()
+        .assert()
+        .success()
+        .stdout("WTFPL (1)\n    left-pad                        1.3.0           LICENSE\n");
+}
+
+#[test]
+#[serial]
+fn licensit_notices() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"either\"\nversion = \"1.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            (
+                "vendor/either/Cargo.toml",
+                "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"MIT\"\n",
+            ),
+            ("vendor/either/LICENSE-MIT", "Copyright (c) 2015 The either authors\n"),
+        ],
+    );
+    create_licensit_notices_command()
+        .env("CARGO_HOME", temp_dir_context.path().join("cargo"))
+        .arg("--notices-format=markdown")
+        .assert()
+        .success()
+        .stdout("Created THIRD-PARTY-NOTICES.md\n");
+    let notices =
+        fs::read_to_string(temp_dir_context.path().join("THIRD-PARTY-NOTICES.md")).unwrap();
+    assert!(notices.contains("- either 1.9.0: MIT\n"));
+    assert!(notices.contains(
+        "## LICENSE-MIT\n\nUsed by: either 1.9.0\n\n```\nCopyright (c) 2015 The either authors\n```\n"
+    ));
+}
+
+#[test]
+#[serial]
+fn licensit_sbom() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"either\"\nversion = \"1.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            (
+                "vendor/either/Cargo.toml",
+                "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+            ),
+            ("LICENSE", MIT_LICENSE),
+        ],
+    );
+    create_licensit_sbom_command()
+        .env("CARGO_HOME", temp_dir_context.path().join("cargo"))
+        .args(["--sbom-format", "spdx-tv", "--output", "sbom.spdx"])
+        .assert()
+        .success()
+        .stdout("Created sbom.spdx\n");
+    let sbom = fs::read_to_string(temp_dir_context.path().join("sbom.spdx")).unwrap();
+    assert!(sbom.starts_with("SPDXVersion: SPDX-2.3\n"));
+    assert!(sbom.contains("PackageLicenseConcluded: MIT\nPackageLicenseDeclared: NOASSERTION\n"));
+    assert!(sbom.contains("PackageLicenseDeclared: MIT OR Apache-2.0\n"));
+    assert!(sbom.contains("ExternalRef: PACKAGE-MANAGER purl pkg:cargo/either@1.9.0\n"));
+}
+
+#[test]
+#[serial]
+fn licensit_policy_check() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"either\"\nversion = \"1.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"server\"\nversion = \"0.3.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            (
+                "vendor/either/Cargo.toml",
+                "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"AGPL-3.0-only OR MIT\"\n",
+            ),
+            (
+                "vendor/server/Cargo.toml",
+                "[package]\nname = \"server\"\nversion = \"0.3.0\"\nlicense = \"MIT AND AGPL-3.0-only\"\n",
+            ),
+            (
+                ".licensit.toml",
+                "[policy]\nallow = [\"MIT\"]\ndeny = [\"AGPL-3.0-only\"]\n",
+            ),
+        ],
+    );
+    create_licensit_policy_command()
+        .env("CARGO_HOME", temp_dir_context.path().join("cargo"))
+        .arg("check")
+        .assert()
+        .failure()
+        .stdout("Denied (1)\n    server                          0.3.0           MIT AND AGPL-3.0-only (because of AGPL-3.0-only)\n1 of 2 dependencies violate the policy\n");
+}
+
+#[test]
+#[serial]
+fn licensit_compatibility() {
+    let temp_dir_context = TempDirContext::new();
+    create_project_files(
+        temp_dir_context.path(),
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"either\"\nversion = \"1.9.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"readline\"\nversion = \"0.3.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            (
+                "vendor/either/Cargo.toml",
+                "[package]\nname = \"either\"\nversion = \"1.9.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+            ),
+            (
+                "vendor/readline/Cargo.toml",
+                "[package]\nname = \"readline\"\nversion = \"0.3.0\"\nlicense = \"GPL-3.0-only\"\n",
+            ),
+            ("LICENSE", MIT_LICENSE),
+        ],
+    );
+    create_licensit_compatibility_command()
+        .env("CARGO_HOME", temp_dir_context.path().join("cargo"))
+        .assert()
+        .failure()
+        .stdout("Project license: MIT\nIncompatible (1)\n    readline                        0.3.0           GPL-3.0-only\n        GPL-3.0-only: The GPL requires the combined work to be distributed under the GPL as a whole\n1 of 2 dependencies are incompatible with the project license\n");
+    create_licensit_compatibility_command()
+        .env("CARGO_HOME", temp_dir_context.path().join("cargo"))
+        .args(["--license", "gpl-3.0"])
+        .assert()
+        .success()
+        .stdout("Project license: GPL-3.0-only\nNo known incompatibility with the licenses of 2 dependencies\n");
+}